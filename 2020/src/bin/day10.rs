@@ -0,0 +1,6 @@
+use adventofcode::runner;
+use year2020::day10::Day10;
+
+fn main() {
+    runner::main::<Day10>();
+}
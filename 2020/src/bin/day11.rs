@@ -0,0 +1,6 @@
+use adventofcode::runner;
+use year2020::day11::Day11;
+
+fn main() {
+    runner::main::<Day11>();
+}
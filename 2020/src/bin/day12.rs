@@ -0,0 +1,6 @@
+use adventofcode::runner;
+use year2020::day12::Day12;
+
+fn main() {
+    runner::main::<Day12>();
+}
@@ -0,0 +1,6 @@
+use adventofcode::runner;
+use year2020::day13::Day13;
+
+fn main() {
+    runner::main::<Day13>();
+}
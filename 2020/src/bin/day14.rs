@@ -0,0 +1,6 @@
+use adventofcode::runner;
+use year2020::day14::Day14;
+
+fn main() {
+    runner::main::<Day14>();
+}
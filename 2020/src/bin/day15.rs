@@ -0,0 +1,6 @@
+use adventofcode::runner;
+use year2020::day15::Day15;
+
+fn main() {
+    runner::main::<Day15>();
+}
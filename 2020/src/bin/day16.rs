@@ -0,0 +1,6 @@
+use adventofcode::runner;
+use year2020::day16::Day16;
+
+fn main() {
+    runner::main::<Day16>();
+}
@@ -0,0 +1,6 @@
+use adventofcode::runner;
+use year2020::day17::Day17;
+
+fn main() {
+    runner::main::<Day17>();
+}
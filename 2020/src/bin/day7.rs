@@ -0,0 +1,6 @@
+use adventofcode::runner;
+use year2020::day07::Day07;
+
+fn main() {
+    runner::main::<Day07>();
+}
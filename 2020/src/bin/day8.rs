@@ -0,0 +1,6 @@
+use adventofcode::runner;
+use year2020::day08::Day08;
+
+fn main() {
+    runner::main::<Day08>();
+}
@@ -0,0 +1,6 @@
+use adventofcode::runner;
+use year2020::day09::Day09;
+
+fn main() {
+    runner::main::<Day09>();
+}
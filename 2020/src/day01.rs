@@ -0,0 +1,105 @@
+use crate::gen::{self, Rng};
+use adventofcode::runner::Puzzle;
+use adventofcode::sums::find_pair;
+use adventofcode::{fs, Answer, Error};
+
+const YEAR: u32 = 2020;
+
+/// Product of the two entries summing to 2020.
+pub fn pair_product(numbers: &[u32]) -> Option<u32> {
+    let mut sorted = numbers.to_vec();
+    sorted.sort_unstable();
+    find_pair(&sorted, YEAR).map(|(a, b)| a * b)
+}
+
+/// Product of the three entries summing to 2020.
+pub fn trit_product(numbers: &[u32]) -> Option<u32> {
+    let mut sorted = numbers.to_vec();
+    sorted.sort_unstable();
+    sorted
+        .iter()
+        .enumerate()
+        .take_while(|(_, &a)| a <= YEAR)
+        .find_map(|(i, &a)| find_pair(&sorted[i + 1..], YEAR - a).map(|(b, c)| a * b * c))
+}
+
+pub fn solve_part1(input: &str) -> Result<Option<u32>, Error> {
+    Ok(pair_product(&Day01::parse(input)?))
+}
+
+pub fn solve_part2(input: &str) -> Result<Option<u32>, Error> {
+    Ok(trit_product(&Day01::parse(input)?))
+}
+
+pub struct Day01;
+
+impl Puzzle for Day01 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 1;
+    type Input = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        fs::parse_str(input, "\n")
+    }
+
+    fn part1(numbers: &Self::Input) -> Option<Answer> {
+        pair_product(numbers).map(Answer::from)
+    }
+
+    fn part2(numbers: &Self::Input) -> Option<Answer> {
+        trit_product(numbers).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, options: &gen::Options) -> Option<String> {
+        Some(gen::expense_report(rng, options.size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adventofcode::{combinator, prop};
+
+    fn pair_product_reference(numbers: &[u32]) -> Option<u32> {
+        let mut comb = combinator::pairs(numbers.to_vec());
+        comb.find(|(a, b)| a + b == YEAR).map(|(a, b)| a * b)
+    }
+
+    fn trit_product_reference(numbers: &[u32]) -> Option<u32> {
+        let mut comb = combinator::trits(numbers.to_vec());
+        comb.find(|(a, b, c)| a + b + c == YEAR)
+            .map(|(a, b, c)| a * b * c)
+    }
+
+    fn expenses(rng: &mut Rng) -> Vec<u32> {
+        let size = rng.range(5, 40);
+        fs::parse_str(&gen::expense_report(rng, size), "\n").unwrap()
+    }
+
+    const PAIRS: &[&dyn prop::Equivalence] = &[
+        &prop::Pair {
+            name: "day1 pairs",
+            generate: expenses,
+            reference: |v| pair_product_reference(v),
+            optimised: |v| pair_product(v),
+        },
+        &prop::Pair {
+            name: "day1 trits",
+            generate: expenses,
+            reference: |v| trit_product_reference(v),
+            optimised: |v| trit_product(v),
+        },
+    ];
+
+    #[test]
+    fn pairs_agree() {
+        prop::check_all(PAIRS);
+    }
+
+    #[test]
+    fn no_solution() {
+        assert_eq!(pair_product(&[]), None);
+        assert_eq!(pair_product(&[1010]), None);
+        assert_eq!(trit_product(&[1000, 1020]), None);
+    }
+}
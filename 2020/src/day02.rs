@@ -0,0 +1,127 @@
+use std::str::FromStr;
+
+use crate::gen::{self, Rng};
+use adventofcode::parser::{self, Parser};
+use adventofcode::runner::Puzzle;
+use adventofcode::{fs, Answer, Error};
+
+pub struct Password {
+    pub data: String,
+    pub chr: String,
+    pub num1: usize,
+    pub num2: usize,
+}
+
+impl FromStr for Password {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Positions in the second policy are 1-based, so 0 is never valid.
+        let position = || {
+            parser::unsigned::<usize>().map_res(|n| match n {
+                0 => Err(String::from("a position of at least 1")),
+                n => Ok(n),
+            })
+        };
+        let policy = position()
+            .and(parser::char('-').then(position()))
+            .skip(parser::char(' '))
+            .and(parser::letter())
+            .skip(parser::tag(": "))
+            .and(parser::rest());
+        let (((num1, num2), chr), data) = parser::parse_all(&policy, s)?;
+        Ok(Password {
+            data: String::from(data),
+            chr: chr.to_string(),
+            num1,
+            num2,
+        })
+    }
+}
+
+pub fn password_validate_one(pwd: &Password) -> bool {
+    let count = pwd.data.matches(&pwd.chr).count();
+    count >= pwd.num1 && count <= pwd.num2
+}
+
+pub fn password_validate_two(pwd: &Password) -> bool {
+    let ch1 = pwd.data.get(pwd.num1 - 1..pwd.num1);
+    let ch2 = pwd.data.get(pwd.num2 - 1..pwd.num2);
+    if let (Some(ch1), Some(ch2)) = (ch1, ch2) {
+        (ch1 == pwd.chr) ^ (ch2 == pwd.chr)
+    } else {
+        false
+    }
+}
+
+pub fn solve_part1(input: &str) -> Result<usize, Error> {
+    let data = Day02::parse(input)?;
+    Ok(data.iter().filter(|p| password_validate_one(p)).count())
+}
+
+pub fn solve_part2(input: &str) -> Result<usize, Error> {
+    let data = Day02::parse(input)?;
+    Ok(data.iter().filter(|p| password_validate_two(p)).count())
+}
+
+pub struct Day02;
+
+impl Puzzle for Day02 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 2;
+    type Input = Vec<Password>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        fs::parse_str(input, "\n")
+    }
+
+    fn part1(data: &Self::Input) -> Option<Answer> {
+        let answer = data.iter().filter(|p| password_validate_one(p)).count();
+        Some(Answer::from(answer))
+    }
+
+    fn part2(data: &Self::Input) -> Option<Answer> {
+        let answer = data.iter().filter(|p| password_validate_two(p)).count();
+        Some(Answer::from(answer))
+    }
+
+    fn generate(rng: &mut Rng, options: &gen::Options) -> Option<String> {
+        Some(gen::passwords(rng, options.size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_policy() {
+        let pwd: Password = "1-3 a: abcde".parse().unwrap();
+        assert_eq!((pwd.num1, pwd.num2, pwd.chr.as_str()), (1, 3, "a"));
+        assert_eq!(pwd.data, "abcde");
+        let pwd: Password = "12-14 z: ".parse().unwrap();
+        assert_eq!((pwd.num1, pwd.num2, pwd.data.as_str()), (12, 14, ""));
+        assert!("0-3 a: abc".parse::<Password>().is_err());
+    }
+
+    #[test]
+    fn reject_malformed() {
+        for line in [
+            "1-3 a abcde",
+            "1 a: abcde",
+            "1-3 : abcde",
+            "a-3 a: x",
+            "1-0 a: x",
+        ] {
+            assert!(line.parse::<Password>().is_err(), "{}", line);
+        }
+        match "1-x a: abcde".parse::<Password>() {
+            Err(Error::Syntax(e)) => assert_eq!(e.position, 2),
+            _ => panic!("expected a syntax error"),
+        }
+        let err = Day02::parse("1-3 a: abcde\n1-x a: abcde\n").err().unwrap();
+        assert!(err
+            .to_string()
+            .contains("line 2: `1-x a: abcde`: syntax error at 2"));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::gen::{self, Rng};
+use adventofcode::grid::Grid;
+use adventofcode::point::{Point, Vector};
+use adventofcode::runner::Puzzle;
+use adventofcode::{Answer, Error};
+
+#[derive(Debug)]
+pub enum Cell {
+    Tree,
+    Space,
+}
+
+/// The slope, repeating to the right.
+pub struct Map {
+    grid: Grid<Cell>,
+}
+
+impl Map {
+    pub fn get(&self, at: Point) -> Option<&Cell> {
+        let width = self.grid.width() as i64;
+        let y = usize::try_from(at.y).ok()?;
+        self.grid.get(at.x.rem_euclid(width) as usize, y)
+    }
+}
+
+/// Walks down the map from the top left corner.
+pub struct Router<'a> {
+    pos: Point,
+    map: &'a Map,
+}
+
+impl<'a> Router<'a> {
+    pub fn new(map: &'a Map) -> Self {
+        Self {
+            pos: Point::ORIGIN,
+            map,
+        }
+    }
+
+    pub fn step(&mut self, slope: Vector) {
+        self.pos += slope;
+    }
+
+    pub fn current(&self) -> Option<&Cell> {
+        self.map.get(self.pos)
+    }
+}
+
+/// Trees met following `slope`, `x` to the right and `y` down, until the
+/// bottom of the map. None unless the slope goes down, as it would never
+/// reach the bottom.
+pub fn count_trees(map: &Map, slope: Vector) -> Option<usize> {
+    if slope.y <= 0 {
+        return None;
+    }
+    let mut answer = 0;
+    let mut router = Router::new(map);
+    while let Some(cell) = router.current() {
+        if matches!(cell, Cell::Tree) {
+            answer += 1;
+        }
+        router.step(slope);
+    }
+    Some(answer)
+}
+
+/// Trees met going right 3, down 1.
+pub fn trees_on_slope(map: &Map) -> Option<usize> {
+    count_trees(map, Vector::new(3, 1))
+}
+
+/// Product of the trees met on all five slopes.
+pub fn slopes_product(map: &Map) -> Option<usize> {
+    [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]
+        .into_iter()
+        .map(|(x, y)| count_trees(map, Vector::new(x, y)))
+        .product()
+}
+
+pub fn solve_part1(input: &str) -> Result<Option<usize>, Error> {
+    Ok(trees_on_slope(&Day03::parse(input)?))
+}
+
+pub fn solve_part2(input: &str) -> Result<Option<usize>, Error> {
+    Ok(slopes_product(&Day03::parse(input)?))
+}
+
+pub struct Day03;
+
+impl Puzzle for Day03 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 3;
+    type Input = Map;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let grid = Grid::parse(input, |c| match c {
+            '.' => Some(Cell::Space),
+            '#' => Some(Cell::Tree),
+            _ => None,
+        })?;
+        Ok(Map { grid })
+    }
+
+    fn part1(map: &Self::Input) -> Option<Answer> {
+        trees_on_slope(map).map(Answer::from)
+    }
+
+    fn part2(map: &Self::Input) -> Option<Answer> {
+        slopes_product(map).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, options: &gen::Options) -> Option<String> {
+        Some(gen::tree_grid(rng, options.size, options.width))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slopes_must_go_down() {
+        let map = Day03::parse("..#\n#..\n.#.").unwrap();
+        assert_eq!(count_trees(&map, Vector::new(2, 1)), Some(1));
+        assert_eq!(count_trees(&map, Vector::new(1, 0)), None);
+        assert_eq!(count_trees(&map, Vector::new(0, -1)), None);
+    }
+}
@@ -0,0 +1,405 @@
+use crate::gen::{self, Rng};
+use adventofcode::interval::Interval;
+use adventofcode::parser::{self, Parser};
+use adventofcode::record;
+use adventofcode::runner::Puzzle;
+use adventofcode::{fs, Answer, Error};
+use std::str::FromStr;
+
+pub trait Field {
+    fn is_filled(&self) -> bool;
+    fn set_value<V: Into<String>>(&mut self, val: V);
+    fn verify(&self) -> bool;
+}
+
+#[derive(Debug, Default)]
+pub struct YearField<const L: u32, const M: u32> {
+    value: Option<String>,
+}
+
+impl<const L: u32, const M: u32> Field for YearField<L, M> {
+    fn is_filled(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn set_value<V: Into<String>>(&mut self, val: V) {
+        self.value = Some(val.into())
+    }
+
+    fn verify(&self) -> bool {
+        if let Some(Ok(val)) = self.value.as_ref().map(|v| v.parse::<u32>()) {
+            Interval::new(L, M).contains(val)
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct HexColorField {
+    value: Option<String>,
+}
+
+impl Field for HexColorField {
+    fn is_filled(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn set_value<V: Into<String>>(&mut self, val: V) {
+        self.value = Some(val.into())
+    }
+
+    fn verify(&self) -> bool {
+        if let Some(val) = &self.value {
+            let hex = parser::char('#').then(parser::take(
+                6,
+                "hex digits",
+                |c| matches!(c, '0'..='9' | 'a'..='f'),
+            ));
+            parser::parse_all(&hex, val).is_ok()
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ColorField {
+    value: Option<String>,
+}
+
+impl Field for ColorField {
+    fn is_filled(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn set_value<V: Into<String>>(&mut self, val: V) {
+        self.value = Some(val.into())
+    }
+
+    fn verify(&self) -> bool {
+        if let Some(val) = &self.value {
+            let color = parser::one_of(&["amb", "blu", "brn", "gry", "grn", "hzl", "oth"]);
+            parser::parse_all(&color, val).is_ok()
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct IdField {
+    value: Option<String>,
+}
+
+impl Field for IdField {
+    fn is_filled(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn set_value<V: Into<String>>(&mut self, val: V) {
+        self.value = Some(val.into())
+    }
+
+    fn verify(&self) -> bool {
+        if let Some(val) = &self.value {
+            let id = parser::take(9, "digits", |c| c.is_ascii_digit());
+            parser::parse_all(&id, val).is_ok()
+        } else {
+            false
+        }
+    }
+}
+
+const CENTIMETRES: Interval<u32> = Interval::new(150, 193);
+const INCHES: Interval<u32> = Interval::new(59, 76);
+
+#[derive(Debug, Default)]
+pub struct UnitField {
+    value: Option<String>,
+}
+
+impl Field for UnitField {
+    fn is_filled(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn set_value<V: Into<String>>(&mut self, val: V) {
+        self.value = Some(val.into())
+    }
+
+    fn verify(&self) -> bool {
+        if let Some(val) = &self.value {
+            let height = parser::unsigned::<u32>().and(parser::tag("cm").or(parser::tag("in")));
+            if let Ok((val, unit)) = parser::parse_all(&height, val) {
+                return match unit {
+                    "cm" => CENTIMETRES.contains(val),
+                    _ => INCHES.contains(val),
+                };
+            }
+        }
+        false
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct IgnoreField;
+
+impl Field for IgnoreField {
+    fn is_filled(&self) -> bool {
+        true
+    }
+
+    fn set_value<V: Into<String>>(&mut self, _val: V) {}
+
+    fn verify(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Passport {
+    pub byr: YearField<1920, 2002>,
+    pub iyr: YearField<2010, 2020>,
+    pub eyr: YearField<2020, 2030>,
+    pub hgt: UnitField,
+    pub hcl: HexColorField,
+    pub ecl: ColorField,
+    pub pid: IdField,
+    pub cid: IgnoreField,
+}
+
+impl Passport {
+    pub fn validate_fill(&self) -> bool {
+        self.byr.is_filled()
+            && self.iyr.is_filled()
+            && self.eyr.is_filled()
+            && self.hgt.is_filled()
+            && self.hcl.is_filled()
+            && self.ecl.is_filled()
+            && self.pid.is_filled()
+            && self.cid.is_filled()
+    }
+
+    pub fn validate(&self) -> bool {
+        self.byr.verify()
+            && self.iyr.verify()
+            && self.eyr.verify()
+            && self.hgt.verify()
+            && self.hcl.verify()
+            && self.ecl.verify()
+            && self.pid.verify()
+            && self.cid.verify()
+    }
+}
+
+impl FromStr for Passport {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut pass = Passport::default();
+        for token in record::tokens(s) {
+            let (key, val, _) = token?;
+            match key {
+                "byr" => pass.byr.set_value(val),
+                "iyr" => pass.iyr.set_value(val),
+                "eyr" => pass.eyr.set_value(val),
+                "hgt" => pass.hgt.set_value(val),
+                "hcl" => pass.hcl.set_value(val),
+                "ecl" => pass.ecl.set_value(val),
+                "pid" => pass.pid.set_value(val),
+                "cid" => pass.cid.set_value(val),
+                _ => {}
+            }
+        }
+        Ok(pass)
+    }
+}
+
+pub fn solve_part1(input: &str) -> Result<usize, Error> {
+    let input = Day04::parse(input)?;
+    Ok(input.iter().filter(|p| p.validate_fill()).count())
+}
+
+pub fn solve_part2(input: &str) -> Result<usize, Error> {
+    let input = Day04::parse(input)?;
+    Ok(input.iter().filter(|p| p.validate()).count())
+}
+
+pub struct Day04;
+
+impl Puzzle for Day04 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 4;
+    type Input = Vec<Passport>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        fs::parse_str(input, "\n\n")
+    }
+
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        let answer = input.iter().filter(|p| p.validate_fill()).count();
+        Some(Answer::from(answer))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        let answer = input.iter().filter(|p| p.validate()).count();
+        Some(Answer::from(answer))
+    }
+
+    fn generate(rng: &mut Rng, options: &gen::Options) -> Option<String> {
+        Some(gen::passports(rng, options.size, options.valid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn year_field() {
+        let mut f = YearField::<1920, 2002>::default();
+        assert!(!f.is_filled());
+        assert!(!f.verify());
+        f.set_value("1908");
+        assert!(f.is_filled());
+        assert!(!f.verify());
+        f.set_value("1921");
+        assert!(f.verify());
+        f.set_value("2003");
+        assert!(!f.verify());
+        f.set_value("2002");
+        assert!(f.verify());
+    }
+
+    #[test]
+    fn hex_field() {
+        let mut f = HexColorField::default();
+        assert!(!f.is_filled());
+        assert!(!f.verify());
+        f.set_value("unknown");
+        assert!(f.is_filled());
+        assert!(!f.verify());
+        f.set_value("#123");
+        assert!(!f.verify());
+        f.set_value("#a123");
+        assert!(!f.verify());
+        f.set_value("#abf123");
+        assert!(f.verify());
+    }
+
+    #[test]
+    fn color_field() {
+        let mut f = ColorField::default();
+        assert!(!f.is_filled());
+        assert!(!f.verify());
+        f.set_value("unknown");
+        assert!(f.is_filled());
+        f.set_value("amb");
+        assert!(f.verify());
+        f.set_value("blu");
+        assert!(f.verify());
+        f.set_value("brn");
+        assert!(f.verify());
+        f.set_value("gry");
+        assert!(f.verify());
+        f.set_value("grn");
+        assert!(f.verify());
+        f.set_value("hzl");
+        assert!(f.verify());
+        f.set_value("oth");
+        assert!(f.verify());
+        f.set_value("red");
+        assert!(!f.verify());
+        f.set_value("ambblu");
+        assert!(!f.verify());
+    }
+
+    #[test]
+    fn pid_field() {
+        let mut f = IdField::default();
+        assert!(!f.is_filled());
+        assert!(!f.verify());
+        f.set_value("unknown");
+        assert!(f.is_filled());
+        f.set_value("02.2");
+        assert!(!f.verify());
+        f.set_value("02333");
+        assert!(!f.verify());
+        f.set_value("02333434");
+        assert!(!f.verify());
+        f.set_value("023334349");
+        assert!(f.verify());
+        f.set_value("0123456789");
+        assert!(!f.verify());
+    }
+
+    #[test]
+    fn passport_tokens() {
+        let pass: Passport =
+            "hcl:#ae17e1 iyr:2013\neyr:2024 ecl:brn pid:760753108 byr:1931 hgt:179cm"
+                .parse()
+                .unwrap();
+        assert!(pass.validate());
+        let pass: Passport = "hgt:1ü0cm byr:1931".parse().unwrap();
+        assert!(!pass.hgt.verify());
+        assert!(pass.byr.verify());
+        assert!(matches!(
+            "byr:1931 byr:1932".parse::<Passport>(),
+            Err(Error::Record(record::RecordError::Duplicate { .. }))
+        ));
+        assert!("byr:1931 hgt".parse::<Passport>().is_err());
+    }
+
+    #[test]
+    fn record_errors_reach_the_input() {
+        let err = Day04::parse("ecl:gry pid:1\n\nbyr:1931\nbyr:1932\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "error: line 3: duplicate key `byr` at 9..17, first seen at 0..8"
+        );
+        let err = Day04::parse("ecl:gry\n\nbyr:1931 hgt\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "error: line 3: `byr:1931 hgt`: token `hgt` at 9..12 has no `:`"
+        );
+    }
+
+    #[test]
+    fn ignore_field() {
+        let f = IgnoreField;
+        assert!(f.is_filled());
+        assert!(f.verify());
+    }
+
+    #[test]
+    fn unit_field() {
+        let mut f = UnitField::default();
+        assert!(!f.is_filled());
+        assert!(!f.verify());
+        f.set_value("unknown");
+        assert!(f.is_filled());
+        f.set_value("190in");
+        assert!(!f.verify());
+        f.set_value("60");
+        assert!(!f.verify());
+        f.set_value("59in");
+        assert!(f.verify());
+        f.set_value("77in");
+        assert!(!f.verify());
+        f.set_value("150cm");
+        assert!(f.verify());
+        f.set_value("194cm");
+        assert!(!f.verify());
+        f.set_value("190mm");
+        assert!(!f.verify());
+        f.set_value("190cm");
+        assert!(f.verify());
+        f.set_value("60in");
+        assert!(f.verify());
+        f.set_value("x60in");
+        assert!(!f.verify());
+        f.set_value("60inch");
+        assert!(!f.verify());
+    }
+}
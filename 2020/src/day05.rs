@@ -0,0 +1,141 @@
+use crate::gen::{self, Rng};
+use adventofcode::bits::{self, BitsError};
+use adventofcode::runner::Puzzle;
+use adventofcode::{fs, Answer, Error};
+use std::collections::HashSet;
+
+/// The seat id spelled by a boarding pass, `B` and `R` being ones.
+pub fn decode(route: &str) -> Result<usize, BitsError> {
+    let id = bits::parse_bits(route, |c| match c {
+        'B' | 'R' => Some(true),
+        'F' | 'L' => Some(false),
+        _ => None,
+    })?;
+    Ok(id as usize)
+}
+
+pub fn calculate_seat(route: &str) -> Result<(usize, usize), BitsError> {
+    let id = decode(route)?;
+    Ok((id >> 3, id & 7))
+}
+
+pub fn highest_id(seats: &HashSet<(usize, usize)>) -> Option<usize> {
+    seats.iter().map(|s| s.0 * 8 + s.1).max()
+}
+
+/// First empty seat after the occupied ones begin.
+pub fn free_seat(seats: &HashSet<(usize, usize)>) -> Option<usize> {
+    let mut skip_begin = true;
+    for row in 0..128 {
+        for col in 0..8 {
+            if !seats.contains(&(row, col)) {
+                if !skip_begin {
+                    return Some(row * 8 + col);
+                }
+            } else {
+                skip_begin = false;
+            }
+        }
+    }
+    None
+}
+
+pub fn solve_part1(input: &str) -> Result<Option<usize>, Error> {
+    Ok(highest_id(&Day05::parse(input)?))
+}
+
+pub fn solve_part2(input: &str) -> Result<Option<usize>, Error> {
+    Ok(free_seat(&Day05::parse(input)?))
+}
+
+pub struct Day05;
+
+impl Puzzle for Day05 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 5;
+    type Input = HashSet<(usize, usize)>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let data: Vec<String> = fs::parse_str(input, "\n")?;
+        let seats = data.iter().map(|route| calculate_seat(route));
+        Ok(seats.collect::<Result<_, _>>()?)
+    }
+
+    fn part1(seats: &Self::Input) -> Option<Answer> {
+        highest_id(seats).map(Answer::from)
+    }
+
+    fn part2(seats: &Self::Input) -> Option<Answer> {
+        free_seat(seats).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, options: &gen::Options) -> Option<String> {
+        Some(gen::boarding_passes(rng, options.size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adventofcode::prop;
+
+    fn calculate_num(route: &str, max: usize) -> usize {
+        let mut begin = 0;
+        let mut end = max;
+        let mut ret = 0;
+        for c in route.chars() {
+            let half = ((end - begin) as f64 / 2.0).ceil() as usize;
+            match c {
+                'F' | 'L' => {
+                    end -= half;
+                    ret = begin;
+                }
+                'B' | 'R' => {
+                    begin += half;
+                    ret = end;
+                }
+                _ => {}
+            }
+        }
+        ret
+    }
+
+    fn calculate_seat_reference(route: &str) -> (usize, usize) {
+        let row = calculate_num(&route[0..7], 127);
+        let col = calculate_num(&route[7..], 7);
+        (row, col)
+    }
+
+    fn calculate_id(route: &str) -> usize {
+        let seat = calculate_seat(route).unwrap();
+        seat.0 * 8 + seat.1
+    }
+
+    #[test]
+    fn test_answer_one() {
+        assert_eq!(calculate_id("BFFFBBFRRR"), 567);
+        assert_eq!(calculate_id("FFFBBBFRRR"), 119);
+        assert_eq!(calculate_id("BBFFBBFRLL"), 820);
+        assert!(Day05::parse("BFFFBBFRRR\nBFFFXBFRRR\n").is_err());
+    }
+
+    const PAIRS: &[&dyn prop::Equivalence] = &[&prop::Pair {
+        name: "day5 seats",
+        generate: |rng| {
+            let size = rng.range(3, 100);
+            prop::Lines::from(gen::boarding_passes(rng, size))
+        },
+        reference: |routes| {
+            routes
+                .iter()
+                .map(|r| Ok(calculate_seat_reference(r)))
+                .collect::<Vec<_>>()
+        },
+        optimised: |routes| routes.iter().map(|r| calculate_seat(r)).collect::<Vec<_>>(),
+    }];
+
+    #[test]
+    fn pairs_agree() {
+        prop::check_all(PAIRS);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::gen::{self, Rng};
+use adventofcode::runner::Puzzle;
+use adventofcode::{fs, Answer, Error};
+use std::str::FromStr;
+
+/// Answers of each person, one bit per question `a..=z`.
+#[derive(Debug)]
+pub struct Group {
+    persons: Vec<u32>,
+}
+
+impl FromStr for Group {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let persons = s
+            .split('\n')
+            .map(|a| {
+                a.bytes().try_fold(0, |acc, b| match b {
+                    b'a'..=b'z' => Ok(acc | 1 << (b - b'a')),
+                    _ => Err(Error::Parse(String::from(a))),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Group { persons })
+    }
+}
+
+impl Group {
+    pub fn count_anyone(&self) -> usize {
+        self.persons.iter().fold(0, |acc, p| acc | p).count_ones() as usize
+    }
+
+    pub fn count_everyone(&self) -> usize {
+        let all = self.persons.iter().copied().reduce(|acc, p| acc & p);
+        all.unwrap_or(0).count_ones() as usize
+    }
+}
+
+pub fn solve_part1(input: &str) -> Result<usize, Error> {
+    let data = Day06::parse(input)?;
+    Ok(data.iter().fold(0, |acc, g| acc + g.count_anyone()))
+}
+
+pub fn solve_part2(input: &str) -> Result<usize, Error> {
+    let data = Day06::parse(input)?;
+    Ok(data.iter().fold(0, |acc, g| acc + g.count_everyone()))
+}
+
+pub struct Day06;
+
+impl Puzzle for Day06 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 6;
+    type Input = Vec<Group>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        fs::parse_str(input, "\n\n")
+    }
+
+    fn part1(data: &Self::Input) -> Option<Answer> {
+        let answer = data.iter().fold(0, |acc, g| acc + g.count_anyone());
+        Some(Answer::from(answer))
+    }
+
+    fn part2(data: &Self::Input) -> Option<Answer> {
+        let answer = data.iter().fold(0, |acc, g| acc + g.count_everyone());
+        Some(Answer::from(answer))
+    }
+
+    fn generate(rng: &mut Rng, options: &gen::Options) -> Option<String> {
+        Some(gen::questionnaires(rng, options.size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adventofcode::prop;
+    use std::collections::HashSet;
+
+    fn count_reference(group: &str) -> (usize, usize) {
+        let persons: Vec<HashSet<char>> = group.split('\n').map(|a| a.chars().collect()).collect();
+        let anyone = persons
+            .iter()
+            .fold(HashSet::<char>::new(), |mut acc, p| {
+                acc.extend(p);
+                acc
+            })
+            .len();
+        let seed = persons.first().cloned().unwrap_or_default();
+        let everyone = persons
+            .iter()
+            .skip(1)
+            .fold(seed, |acc, p| acc.intersection(p).cloned().collect())
+            .len();
+        (anyone, everyone)
+    }
+
+    const PAIRS: &[&dyn prop::Equivalence] = &[&prop::Pair {
+        name: "day6 groups",
+        generate: |rng| {
+            let size = rng.range(1, 30);
+            fs::parse_str::<String>(&gen::questionnaires(rng, size), "\n\n").unwrap()
+        },
+        reference: |groups| {
+            groups
+                .iter()
+                .map(|g| count_reference(g))
+                .collect::<Vec<_>>()
+        },
+        optimised: |groups| {
+            groups
+                .iter()
+                .map(|g| {
+                    let group = Group::from_str(g).unwrap();
+                    (group.count_anyone(), group.count_everyone())
+                })
+                .collect::<Vec<_>>()
+        },
+    }];
+
+    #[test]
+    fn pairs_agree() {
+        prop::check_all(PAIRS);
+    }
+}
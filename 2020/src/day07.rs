@@ -0,0 +1,211 @@
+use std::str::FromStr;
+
+use crate::gen::{self, Rng};
+use adventofcode::graph::Graph;
+use adventofcode::parser::{self, PResult, Parser};
+use adventofcode::runner::Puzzle;
+use adventofcode::{fs, Answer, Error};
+
+const GOLD: &str = "shiny gold";
+
+/// `light red bags contain 1 bright white bag, 2 muted yellow bags.`
+#[derive(Debug)]
+pub struct Rule {
+    pub colour: String,
+    pub contents: Vec<(u32, String)>,
+}
+
+/// Two words such as `light red`.
+fn colour<'a>() -> impl Parser<'a, Output = &'a str> {
+    move |input: &'a str| -> PResult<'a, &'a str> {
+        let word = || parser::take_while1("a colour", |c: char| c.is_ascii_lowercase());
+        let (_, rest) = word().skip(parser::char(' ')).and(word()).parse(input)?;
+        Ok((&input[..input.len() - rest.len()], rest))
+    }
+}
+
+impl FromStr for Rule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bags = parser::unsigned::<u32>()
+            .skip(parser::char(' '))
+            .and(colour())
+            .skip(parser::tag(" bag"))
+            .skip(parser::char('s').opt());
+        let contents = parser::tag("no other bags")
+            .map(|_| Vec::new())
+            .or(bags.sep_by(parser::tag(", ")));
+        let rule = colour()
+            .skip(parser::tag(" bags contain "))
+            .and(contents)
+            .skip(parser::char('.'));
+        let (colour, contents) = parser::parse_all(&rule, s)?;
+        Ok(Rule {
+            colour: String::from(colour),
+            contents: contents
+                .into_iter()
+                .map(|(n, c)| (n, String::from(c)))
+                .collect(),
+        })
+    }
+}
+
+/// Containment graph of `rules`, an edge of weight `n` leading from a bag to
+/// each colour it holds `n` of.
+pub fn bag_graph(rules: &[Rule]) -> Graph<u32> {
+    let mut graph = Graph::new();
+    for rule in rules {
+        graph.intern(&rule.colour);
+        for (amount, inner) in &rule.contents {
+            graph.add_edge(&rule.colour, inner, *amount);
+        }
+    }
+    graph
+}
+
+/// Colours that eventually hold a shiny gold bag.
+pub fn holders(graph: &Graph<u32>) -> Result<usize, Error> {
+    let gold = graph.id(GOLD)?;
+    Ok(graph
+        .reachable_from(gold)
+        .into_iter()
+        .filter(|&n| n != gold)
+        .count())
+}
+
+/// Bags inside a single shiny gold bag.
+pub fn inside(graph: &Graph<u32>) -> Result<u64, Error> {
+    Ok(graph.weighted_total(graph.id(GOLD)?)?)
+}
+
+pub fn solve_part1(input: &str) -> Result<usize, Error> {
+    holders(&Day07::parse(input)?)
+}
+
+pub fn solve_part2(input: &str) -> Result<u64, Error> {
+    inside(&Day07::parse(input)?)
+}
+
+pub struct Day07;
+
+impl Puzzle for Day07 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 7;
+    type Input = Graph<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let graph = bag_graph(&fs::parse_str::<Rule>(input, "\n")?);
+        graph.topological_order()?;
+        Ok(graph)
+    }
+
+    fn part1(graph: &Self::Input) -> Option<Answer> {
+        Self::try_part1(graph).ok().flatten()
+    }
+
+    fn part2(graph: &Self::Input) -> Option<Answer> {
+        Self::try_part2(graph).ok().flatten()
+    }
+
+    /// Reports a missing shiny gold bag rather than showing no answer.
+    fn try_part1(graph: &Self::Input) -> Result<Option<Answer>, Error> {
+        Ok(Some(Answer::from(holders(graph)?)))
+    }
+
+    /// Reports a missing shiny gold bag or an overflowing count.
+    fn try_part2(graph: &Self::Input) -> Result<Option<Answer>, Error> {
+        Ok(Some(Answer::from(inside(graph)?)))
+    }
+
+    fn generate(rng: &mut Rng, options: &gen::Options) -> Option<String> {
+        Some(gen::bag_rules(rng, options.size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adventofcode::prop;
+    use std::collections::HashMap;
+
+    fn count_reference(rules: &[String]) -> Option<(usize, u64)> {
+        let rules: HashMap<String, Vec<(u32, String)>> = rules
+            .iter()
+            .map(|r| {
+                let rule = Rule::from_str(r).unwrap();
+                (rule.colour, rule.contents)
+            })
+            .collect();
+        fn holds(rules: &HashMap<String, Vec<(u32, String)>>, bag: &str) -> bool {
+            rules
+                .get(bag)
+                .is_some_and(|c| c.iter().any(|(_, i)| i == GOLD || holds(rules, i)))
+        }
+        fn inside(rules: &HashMap<String, Vec<(u32, String)>>, bag: &str) -> u64 {
+            rules.get(bag).map_or(0, |c| {
+                c.iter()
+                    .map(|(n, i)| *n as u64 * (1 + inside(rules, i)))
+                    .sum()
+            })
+        }
+        let known = rules.contains_key(GOLD) || rules.values().flatten().any(|(_, i)| i == GOLD);
+        let count = rules.keys().filter(|b| holds(&rules, b)).count();
+        known.then(|| (count, inside(&rules, GOLD)))
+    }
+
+    #[test]
+    fn parse_rules() {
+        let rule =
+            Rule::from_str("light red bags contain 1 bright white bag, 2 muted yellow bags.")
+                .unwrap();
+        assert_eq!(rule.colour, "light red");
+        assert_eq!(
+            rule.contents,
+            [
+                (1, String::from("bright white")),
+                (2, String::from("muted yellow"))
+            ]
+        );
+        let rule = Rule::from_str("faded blue bags contain no other bags.").unwrap();
+        assert!(rule.contents.is_empty());
+        assert!(Rule::from_str("faded blue bags contain 2 dotted.").is_err());
+        assert!(Rule::from_str("faded blue bags contain 1 dotted black bag").is_err());
+    }
+
+    #[test]
+    fn reject_cycles() {
+        let input = "a b bags contain 1 c d bag.\nc d bags contain 2 a b bags.\n";
+        let err = Day07::parse(input).unwrap_err();
+        assert_eq!(err.to_string(), "cycle a b -> c d -> a b");
+    }
+
+    #[test]
+    fn missing_gold_is_reported() {
+        let graph = Day07::parse("a b bags contain 1 c d bag.\n").unwrap();
+        assert!(Day07::try_part1(&graph).is_err());
+        assert!(Day07::try_part2(&graph).is_err());
+        assert_eq!(Day07::part1(&graph), None);
+    }
+
+    const PAIRS: &[&dyn prop::Equivalence] = &[&prop::Pair {
+        name: "day7 bags",
+        generate: |rng| {
+            let size = rng.range(1, 60);
+            prop::Lines::from(gen::bag_rules(rng, size))
+        },
+        reference: |rules| count_reference(rules),
+        optimised: |rules| {
+            if rules.is_empty() {
+                return None;
+            }
+            let graph = Day07::parse(&rules.text()).unwrap();
+            Some((holders(&graph).ok()?, inside(&graph).ok()?))
+        },
+    }];
+
+    #[test]
+    fn pairs_agree() {
+        prop::check_all(PAIRS);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::gen::{self, Rng};
+use adventofcode::runner::Puzzle;
+use adventofcode::vm::{self, Exit, Instruction, Machine};
+use adventofcode::{Answer, Error};
+
+/// The accumulator right before any instruction runs a second time.
+pub fn acc_before_loop(program: &[Instruction]) -> Option<i64> {
+    let mut machine = Machine::new(program);
+    match machine.run() {
+        Exit::Loop(_) => Some(machine.acc()),
+        _ => None,
+    }
+}
+
+/// The accumulator after the program is repaired and has terminated.
+pub fn acc_after_repair(program: &[Instruction]) -> Option<i64> {
+    vm::repair(program).map(|r| r.acc)
+}
+
+pub fn solve_part1(input: &str) -> Result<Option<i64>, Error> {
+    Ok(acc_before_loop(&Day08::parse(input)?))
+}
+
+pub fn solve_part2(input: &str) -> Result<Option<i64>, Error> {
+    Ok(acc_after_repair(&Day08::parse(input)?))
+}
+
+pub struct Day08;
+
+impl Puzzle for Day08 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 8;
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        vm::parse_program(input)
+    }
+
+    fn part1(program: &Self::Input) -> Option<Answer> {
+        acc_before_loop(program).map(Answer::from)
+    }
+
+    fn part2(program: &Self::Input) -> Option<Answer> {
+        acc_after_repair(program).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, options: &gen::Options) -> Option<String> {
+        Some(gen::boot_code(rng, options.size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adventofcode::prop;
+    use adventofcode::vm::Op;
+    use std::collections::HashSet;
+
+    /// Straightforward interpreter, returning where it stopped.
+    fn run_reference(program: &[Instruction]) -> (i64, i64) {
+        let (mut acc, mut ip, mut seen) = (0, 0i64, HashSet::new());
+        while (0..program.len() as i64).contains(&ip) && seen.insert(ip) {
+            let ins = program[ip as usize];
+            match ins.op {
+                Op::Acc => acc += ins.arg,
+                Op::Jmp => ip += ins.arg - 1,
+                Op::Nop => {}
+            }
+            ip += 1;
+        }
+        (ip, acc)
+    }
+
+    fn repair_reference(program: &[Instruction]) -> Option<i64> {
+        (0..program.len()).find_map(|ip| {
+            let mut patched = program.to_vec();
+            patched[ip] = patched[ip].flipped()?;
+            let (ip, acc) = run_reference(&patched);
+            (ip == patched.len() as i64).then_some(acc)
+        })
+    }
+
+    const PAIRS: &[&dyn prop::Equivalence] = &[&prop::Pair {
+        name: "day8 boot code",
+        generate: |rng| {
+            let size = rng.range(4, 80);
+            prop::Lines::from(gen::boot_code(rng, size))
+        },
+        reference: |lines| {
+            let program = vm::parse_program(&lines.text()).ok()?;
+            let (ip, acc) = run_reference(&program);
+            let looped = (0..program.len() as i64).contains(&ip);
+            looped.then(|| (acc, repair_reference(&program)))
+        },
+        optimised: |lines| {
+            let program = Day08::parse(&lines.text()).ok()?;
+            Some((acc_before_loop(&program)?, acc_after_repair(&program)))
+        },
+    }];
+
+    #[test]
+    fn pairs_agree() {
+        prop::check_all(PAIRS);
+    }
+}
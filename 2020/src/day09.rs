@@ -0,0 +1,179 @@
+use crate::gen::{self, Rng};
+use adventofcode::runner::Puzzle;
+use adventofcode::sums::{self, PairWindow};
+use adventofcode::{fs, Answer, Error};
+
+/// Preamble of real inputs, used unless the input names another.
+pub const PREAMBLE: usize = 25;
+
+/// The numbers, and how many come before the first one to check. Inputs may
+/// start with a `preamble: N` line, as the puzzle's example does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Xmas {
+    pub preamble: usize,
+    pub numbers: Vec<u64>,
+}
+
+impl Xmas {
+    pub fn first_invalid(&self) -> Option<u64> {
+        first_invalid(&self.numbers, self.preamble)
+    }
+}
+
+/// The first number that is not the sum of two of the `preamble` before it.
+pub fn first_invalid(numbers: &[u64], preamble: usize) -> Option<u64> {
+    let mut window = PairWindow::new(preamble);
+    numbers.iter().copied().find(|&n| {
+        let invalid = window.is_full() && window.pair(n).is_none();
+        window.push(n);
+        invalid
+    })
+}
+
+/// Sum of the smallest and largest number in the contiguous range of at
+/// least two numbers adding up to `target`.
+pub fn weakness(numbers: &[u64], target: u64) -> Option<u64> {
+    let range = &numbers[sums::contiguous_range(numbers, target, 2)?];
+    Some(range.iter().min()? + range.iter().max()?)
+}
+
+pub fn solve_part1(input: &str) -> Result<Option<u64>, Error> {
+    Ok(Day09::parse(input)?.first_invalid())
+}
+
+pub fn solve_part2(input: &str) -> Result<Option<u64>, Error> {
+    let xmas = Day09::parse(input)?;
+    Ok(xmas
+        .first_invalid()
+        .and_then(|n| weakness(&xmas.numbers, n)))
+}
+
+pub struct Day09;
+
+impl Puzzle for Day09 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 9;
+    type Input = Xmas;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let input = input.trim_start();
+        let (preamble, numbers) = match input.strip_prefix("preamble: ") {
+            Some(rest) => {
+                let (preamble, numbers) = rest.split_once('\n').unwrap_or((rest, ""));
+                let preamble = preamble
+                    .trim()
+                    .parse()
+                    .ok()
+                    .filter(|&p| p >= 2)
+                    .ok_or_else(|| Error::Parse(format!("invalid preamble `{}`", preamble)))?;
+                (preamble, numbers)
+            }
+            None => (PREAMBLE, input),
+        };
+        Ok(Xmas {
+            preamble,
+            numbers: fs::parse_str(numbers, "\n")?,
+        })
+    }
+
+    fn part1(xmas: &Self::Input) -> Option<Answer> {
+        xmas.first_invalid().map(Answer::from)
+    }
+
+    fn part2(xmas: &Self::Input) -> Option<Answer> {
+        let invalid = xmas.first_invalid()?;
+        weakness(&xmas.numbers, invalid).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, options: &gen::Options) -> Option<String> {
+        Some(gen::xmas_data(rng, options.size, PREAMBLE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adventofcode::prop;
+
+    fn first_invalid_reference(numbers: &[u64], preamble: usize) -> Option<u64> {
+        (preamble..numbers.len())
+            .find(|&i| {
+                let window = &numbers[i - preamble..i];
+                !window
+                    .iter()
+                    .enumerate()
+                    .any(|(j, a)| window[j + 1..].iter().any(|b| a + b == numbers[i]))
+            })
+            .map(|i| numbers[i])
+    }
+
+    fn weakness_reference(numbers: &[u64], target: u64) -> Option<u64> {
+        (0..numbers.len()).find_map(|end| {
+            (0..end).find_map(|start| {
+                let range = &numbers[start..=end];
+                let sum: u64 = range.iter().sum();
+                (sum == target).then(|| range.iter().min().unwrap() + range.iter().max().unwrap())
+            })
+        })
+    }
+
+    #[test]
+    fn explicit_preamble() {
+        let numbers: Vec<u64> = (1..=25).chain([26, 49, 100, 50]).collect();
+        assert_eq!(first_invalid(&numbers, PREAMBLE), Some(100));
+        let xmas = Day09::parse("1\n2\n3\n").unwrap();
+        assert_eq!((xmas.preamble, xmas.numbers), (PREAMBLE, vec![1, 2, 3]));
+        let xmas = Day09::parse("preamble: 2\n1\n2\n3\n4\n").unwrap();
+        assert_eq!((xmas.preamble, xmas.first_invalid()), (2, Some(4)));
+        assert!(Day09::parse("preamble: many\n1\n2\n").is_err());
+        assert!(Day09::parse("preamble: 0\n1\n2\n").is_err());
+    }
+
+    #[test]
+    fn generated_inputs_keep_their_preamble() {
+        let options = gen::Options {
+            size: 60,
+            ..gen::Options::default()
+        };
+        for seed in 0..20 {
+            let text = Day09::generate(&mut Rng::new(seed), &options).unwrap();
+            let xmas = Day09::parse(&text).unwrap();
+            assert_eq!((xmas.preamble, xmas.numbers.len()), (PREAMBLE, 60));
+            assert_eq!(
+                xmas.first_invalid(),
+                first_invalid_reference(&xmas.numbers, PREAMBLE)
+            );
+            assert!(xmas.first_invalid().is_some());
+        }
+    }
+
+    const PAIRS: &[&dyn prop::Equivalence] = &[&prop::Pair {
+        name: "day9 xmas",
+        generate: |rng| {
+            let (size, preamble) = (rng.range(30, 120), rng.range(4, 8));
+            prop::Lines::from(gen::xmas_data(rng, size, preamble))
+        },
+        reference: |lines| {
+            let (preamble, numbers) = match lines.first()?.strip_prefix("preamble: ") {
+                Some(preamble) => (preamble.parse().ok()?, &lines[1..]),
+                None => (PREAMBLE, &lines[..]),
+            };
+            let numbers: Vec<u64> = numbers
+                .iter()
+                .map(|n| n.parse().ok())
+                .collect::<Option<_>>()?;
+            let invalid = first_invalid_reference(&numbers, preamble)?;
+            Some((invalid, weakness_reference(&numbers, invalid)))
+        },
+        optimised: |lines| {
+            let xmas = Day09::parse(&lines.text()).ok()?;
+            let invalid = xmas.first_invalid()?;
+            Some((invalid, weakness(&xmas.numbers, invalid)))
+        },
+    }];
+
+    #[test]
+    fn pairs_agree() {
+        prop::check_all(PAIRS);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::gen::{self, Rng};
+use adventofcode::dp::{self, DpError};
+use adventofcode::runner::Puzzle;
+use adventofcode::{fs, Answer, Error};
+
+/// Largest joltage difference an adapter accepts.
+const MAX_STEP: u64 = 3;
+
+/// The outlet, every adapter in order and the device, or nothing if some
+/// adapter can never be plugged in after the previous one.
+pub fn chain(adapters: &[u64]) -> Option<Vec<u64>> {
+    let mut chain = vec![0];
+    chain.extend(adapters);
+    chain.sort_unstable();
+    chain.push(chain.last()? + MAX_STEP);
+    let valid = chain
+        .windows(2)
+        .all(|w| (1..=MAX_STEP).contains(&(w[1] - w[0])));
+    valid.then_some(chain)
+}
+
+/// 1-jolt differences multiplied by 3-jolt differences along the chain.
+pub fn difference_product(chain: &[u64]) -> usize {
+    let histogram = dp::differences(chain);
+    let count = |d| histogram.get(&d).copied().unwrap_or(0);
+    count(1) * count(3)
+}
+
+/// Ways to arrange the adapters, counted in `u64` and again in `u128` only
+/// when that overflows.
+pub fn arrangements(chain: &[u64]) -> Result<Answer, Error> {
+    match dp::count_paths::<u64>(chain, MAX_STEP) {
+        Ok(count) => Ok(Answer::from(count)),
+        Err(DpError::Overflow { .. }) => {
+            Ok(Answer::from(dp::count_paths::<u128>(chain, MAX_STEP)?))
+        }
+    }
+}
+
+pub fn solve_part1(input: &str) -> Result<Option<usize>, Error> {
+    Ok(chain(&Day10::parse(input)?).map(|c| difference_product(&c)))
+}
+
+pub fn solve_part2(input: &str) -> Result<Option<Answer>, Error> {
+    Day10::try_part2(&Day10::parse(input)?)
+}
+
+pub struct Day10;
+
+impl Puzzle for Day10 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 10;
+    type Input = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        fs::parse_str(input, "\n")
+    }
+
+    fn part1(adapters: &Self::Input) -> Option<Answer> {
+        chain(adapters).map(|c| Answer::from(difference_product(&c)))
+    }
+
+    fn part2(adapters: &Self::Input) -> Option<Answer> {
+        Self::try_part2(adapters).ok().flatten()
+    }
+
+    /// Reports a count overflowing even `u128` instead of showing no answer.
+    fn try_part2(adapters: &Self::Input) -> Result<Option<Answer>, Error> {
+        chain(adapters).map(|c| arrangements(&c)).transpose()
+    }
+
+    fn generate(rng: &mut Rng, options: &gen::Options) -> Option<String> {
+        Some(gen::adapters(rng, options.size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adventofcode::dp::Memo;
+    use adventofcode::prop;
+
+    /// Arrangements counted from each adapter to the device, top down.
+    fn arrangements_reference(chain: &[u64]) -> u128 {
+        let mut from = Memo::new(|from: &mut dyn FnMut(usize) -> u128, i: usize| {
+            if i + 1 == chain.len() {
+                return 1;
+            }
+            (i + 1..chain.len())
+                .take_while(|&j| chain[j] - chain[i] <= MAX_STEP)
+                .map(from)
+                .sum()
+        });
+        from.get(0)
+    }
+
+    #[test]
+    fn invalid_chain() {
+        assert_eq!(chain(&[1, 2, 6]), None);
+        assert_eq!(chain(&[1, 1, 2]), None);
+        assert_eq!(chain(&[3, 1]), Some(vec![0, 1, 3, 6]));
+    }
+
+    #[test]
+    fn wide_counts() {
+        // Runs of four 1-jolt steps, each allowing 7 arrangements.
+        let adapters: Vec<u64> = (0..40)
+            .flat_map(|r| (0..=4).map(move |i| r * 7 + i))
+            .filter(|&j| j > 0)
+            .collect();
+        let chain = chain(&adapters).unwrap();
+        let want = 7u128.pow(40);
+        assert!(want > u64::MAX as u128);
+        assert_eq!(arrangements(&chain).unwrap(), Answer::from(want));
+        assert_eq!(arrangements_reference(&chain), want);
+    }
+
+    #[test]
+    fn overflow_is_reported() {
+        let adapters: Vec<u64> = (1..=200).collect();
+        let err = Day10::try_part2(&adapters).unwrap_err();
+        assert!(
+            matches!(err, Error::Dp(DpError::Overflow { .. })),
+            "{:?}",
+            err
+        );
+        assert_eq!(Day10::try_part2(&vec![1, 5]).unwrap(), None);
+    }
+
+    const PAIRS: &[&dyn prop::Equivalence] = &[&prop::Pair {
+        name: "day10 adapters",
+        generate: |rng| {
+            let size = rng.range(1, 200);
+            fs::parse_str::<u64>(&gen::adapters(rng, size), "\n").unwrap()
+        },
+        reference: |adapters| chain(adapters).map(|c| Answer::from(arrangements_reference(&c))),
+        optimised: |adapters| chain(adapters).and_then(|c| arrangements(&c).ok()),
+    }];
+
+    #[test]
+    fn pairs_agree() {
+        prop::check_all(PAIRS);
+    }
+}
@@ -0,0 +1,188 @@
+use crate::gen::{self, Rng};
+use adventofcode::automaton::{
+    Adjacent, Automaton, LineOfSight, Neighbourhood, Neighbours, Outcome,
+};
+use adventofcode::grid::Grid;
+use adventofcode::runner::Puzzle;
+use adventofcode::{Answer, Error};
+
+/// Rounds after which a layout that keeps changing is given up on.
+const LIMIT: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Seat {
+    Floor,
+    Empty,
+    Occupied,
+}
+
+/// People sit down where nobody is near and leave once `tolerance` of the
+/// seats they look at are taken.
+fn rule(tolerance: usize) -> impl Fn(&Seat, Neighbours<Seat>) -> Seat {
+    move |seat, near| {
+        let occupied = near.filter(|&&s| s == Seat::Occupied).count();
+        match seat {
+            Seat::Empty if occupied == 0 => Seat::Occupied,
+            Seat::Occupied if occupied >= tolerance => Seat::Empty,
+            seat => *seat,
+        }
+    }
+}
+
+/// Occupied seats once nobody moves any more, if that ever happens.
+pub fn settle<N: Neighbourhood<Seat>>(
+    layout: &Grid<Seat>,
+    neighbourhood: &N,
+    tolerance: usize,
+) -> Option<usize> {
+    let mut automaton = Automaton::new(layout.clone(), neighbourhood);
+    // Real layouts settle, but others may flip between two states forever.
+    match automaton.run(rule(tolerance), LIMIT) {
+        Outcome::Stable { .. } => {}
+        Outcome::Cycle { .. } | Outcome::Unsettled => return None,
+    }
+    let cells = automaton.grid().cells();
+    Some(cells.iter().filter(|&&s| s == Seat::Occupied).count())
+}
+
+pub fn settle_adjacent(layout: &Grid<Seat>) -> Option<usize> {
+    settle(layout, &Adjacent, 4)
+}
+
+pub fn settle_in_sight(layout: &Grid<Seat>) -> Option<usize> {
+    let sight = LineOfSight {
+        transparent: |s: &Seat| *s == Seat::Floor,
+    };
+    settle(layout, &sight, 5)
+}
+
+pub fn solve_part1(input: &str) -> Result<Option<usize>, Error> {
+    Ok(settle_adjacent(&Day11::parse(input)?))
+}
+
+pub fn solve_part2(input: &str) -> Result<Option<usize>, Error> {
+    Ok(settle_in_sight(&Day11::parse(input)?))
+}
+
+pub struct Day11;
+
+impl Puzzle for Day11 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 11;
+    type Input = Grid<Seat>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Grid::parse(input, |c| match c {
+            '.' => Some(Seat::Floor),
+            'L' => Some(Seat::Empty),
+            '#' => Some(Seat::Occupied),
+            _ => None,
+        })
+    }
+
+    fn part1(layout: &Self::Input) -> Option<Answer> {
+        settle_adjacent(layout).map(Answer::from)
+    }
+
+    fn part2(layout: &Self::Input) -> Option<Answer> {
+        settle_in_sight(layout).map(Answer::from)
+    }
+
+    /// Draws layouts until one settles in both parts.
+    fn generate(rng: &mut Rng, options: &gen::Options) -> Option<String> {
+        loop {
+            let text = gen::seat_layout(rng, options.size, options.width);
+            let layout = Self::parse(&text).ok()?;
+            if settle_adjacent(&layout).is_some() && settle_in_sight(&layout).is_some() {
+                return Some(text);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adventofcode::prop;
+
+    /// Rounds on plain rows of bytes, looking around with a nested loop.
+    fn settle_reference(rows: &[String], sight: bool, tolerance: usize) -> Option<usize> {
+        let mut rows: Vec<Vec<u8>> = rows.iter().map(|r| r.bytes().collect()).collect();
+        for _ in 0..1000 {
+            let mut next = rows.clone();
+            for y in 0..rows.len() {
+                for x in 0..rows[y].len() {
+                    let mut occupied = 0;
+                    for dy in -1i64..=1 {
+                        for dx in -1i64..=1 {
+                            if (dx, dy) == (0, 0) {
+                                continue;
+                            }
+                            let (mut cx, mut cy) = (x as i64 + dx, y as i64 + dy);
+                            while cy >= 0
+                                && (cy as usize) < rows.len()
+                                && cx >= 0
+                                && (cx as usize) < rows[0].len()
+                            {
+                                match rows[cy as usize][cx as usize] {
+                                    b'#' => occupied += 1,
+                                    b'.' if sight => {
+                                        cx += dx;
+                                        cy += dy;
+                                        continue;
+                                    }
+                                    _ => {}
+                                }
+                                break;
+                            }
+                        }
+                    }
+                    next[y][x] = match rows[y][x] {
+                        b'L' if occupied == 0 => b'#',
+                        b'#' if occupied >= tolerance => b'L',
+                        c => c,
+                    };
+                }
+            }
+            if next == rows {
+                return Some(rows.iter().flatten().filter(|&&c| c == b'#').count());
+            }
+            rows = next;
+        }
+        None
+    }
+
+    #[test]
+    fn flipping_layout() {
+        // Every seat sees at least four others, so they all empty at once.
+        let layout = Day11::parse(".LLL.\nLLLLL\nLLLLL\nLLLLL\n.LLL.\n").unwrap();
+        assert_eq!(settle_adjacent(&layout), None);
+        assert_eq!(settle_in_sight(&layout), Some(9));
+    }
+
+    const PAIRS: &[&dyn prop::Equivalence] = &[&prop::Pair {
+        name: "day11 seats",
+        generate: |rng| {
+            let (size, width) = (rng.range(1, 12), rng.range(1, 12));
+            prop::Lines::from(gen::seat_layout(rng, size, width))
+        },
+        reference: |rows| {
+            let ok = !rows.is_empty() && rows.iter().all(|r| r.len() == rows[0].len());
+            ok.then(|| {
+                (
+                    settle_reference(rows, false, 4),
+                    settle_reference(rows, true, 5),
+                )
+            })
+        },
+        optimised: |rows| {
+            let layout = Day11::parse(&rows.text()).ok()?;
+            Some((settle_adjacent(&layout), settle_in_sight(&layout)))
+        },
+    }];
+
+    #[test]
+    fn pairs_agree() {
+        prop::check_all(PAIRS);
+    }
+}
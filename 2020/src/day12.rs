@@ -0,0 +1,213 @@
+use std::str::FromStr;
+
+use crate::gen::{self, Rng};
+use adventofcode::parser::{self, Parser};
+use adventofcode::point::{self, Direction, Point, Vector};
+use adventofcode::runner::Puzzle;
+use adventofcode::{fs, Answer, Error};
+
+/// `N3`, `R90` or `F10`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Move(Direction, i64),
+    /// Quarter turns, clockwise when positive.
+    Turn(i64),
+    Forward(i64),
+}
+
+impl FromStr for Action {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let letter = parser::satisfy(String::from("one of NSEWLRF"), |c| "NSEWLRF".contains(c));
+        let action = letter
+            .and(parser::unsigned::<i64>())
+            .map_res(|(letter, n)| match letter {
+                'F' => Ok(Action::Forward(n)),
+                'L' | 'R' => {
+                    let turns = point::quarter_turns(n)
+                        .ok_or_else(|| format!("a multiple of 90 degrees, not {}", n))?;
+                    Ok(Action::Turn(if letter == 'L' { -turns } else { turns }))
+                }
+                c => Direction::from_letter(c)
+                    .map(|d| Action::Move(d, n))
+                    .ok_or_else(|| format!("a direction, not `{}`", c)),
+            });
+        Ok(parser::parse_all(&action, s)?)
+    }
+}
+
+/// What the compass moves and the turns rotate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Navigation {
+    /// The ship itself, which moves forward along its heading.
+    Heading,
+    /// A waypoint relative to the ship, which moves forward towards it.
+    Waypoint,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ship {
+    pub position: Point,
+    /// The heading or the waypoint, depending on the [`Navigation`].
+    pub vector: Vector,
+}
+
+impl Ship {
+    pub fn new(navigation: Navigation) -> Self {
+        let vector = match navigation {
+            Navigation::Heading => Direction::East.vector(),
+            Navigation::Waypoint => Vector::new(10, -1),
+        };
+        Ship {
+            position: Point::ORIGIN,
+            vector,
+        }
+    }
+
+    pub fn apply(&mut self, action: Action, navigation: Navigation) {
+        match (action, navigation) {
+            (Action::Move(d, n), Navigation::Heading) => self.position += d.vector() * n,
+            (Action::Move(d, n), Navigation::Waypoint) => self.vector += d.vector() * n,
+            (Action::Turn(turns), _) => self.vector = self.vector.rotate(turns),
+            (Action::Forward(n), _) => self.position += self.vector * n,
+        }
+    }
+}
+
+/// The ship after following every action.
+pub fn navigate(actions: &[Action], navigation: Navigation) -> Ship {
+    let mut ship = Ship::new(navigation);
+    for &action in actions {
+        ship.apply(action, navigation);
+    }
+    ship
+}
+
+/// Manhattan distance travelled from the start.
+pub fn distance(actions: &[Action], navigation: Navigation) -> u64 {
+    Point::ORIGIN.manhattan(navigate(actions, navigation).position)
+}
+
+pub fn solve_part1(input: &str) -> Result<u64, Error> {
+    Ok(distance(&Day12::parse(input)?, Navigation::Heading))
+}
+
+pub fn solve_part2(input: &str) -> Result<u64, Error> {
+    Ok(distance(&Day12::parse(input)?, Navigation::Waypoint))
+}
+
+pub struct Day12;
+
+impl Puzzle for Day12 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 12;
+    type Input = Vec<Action>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        fs::parse_str(input, "\n")
+    }
+
+    fn part1(actions: &Self::Input) -> Option<Answer> {
+        Some(Answer::from(distance(actions, Navigation::Heading)))
+    }
+
+    fn part2(actions: &Self::Input) -> Option<Answer> {
+        Some(Answer::from(distance(actions, Navigation::Waypoint)))
+    }
+
+    fn generate(rng: &mut Rng, options: &gen::Options) -> Option<String> {
+        Some(gen::navigation(rng, options.size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adventofcode::prop;
+
+    /// Follows the actions with east/north coordinates and the heading in
+    /// degrees, rotating the waypoint one right angle at a time.
+    fn distance_reference(lines: &[String], waypoint: bool) -> i64 {
+        let (mut east, mut north, mut degrees) = (0i64, 0i64, 90i64);
+        let (mut we, mut wn) = (10i64, 1i64);
+        for line in lines {
+            let (action, value) = line.split_at(1);
+            let n: i64 = value.parse().unwrap();
+            let (de, dn) = match action {
+                "N" => (0, n),
+                "S" => (0, -n),
+                "E" => (n, 0),
+                "W" => (-n, 0),
+                _ => (0, 0),
+            };
+            match action {
+                "L" | "R" => {
+                    let turn = if action == "R" { n } else { 360 - n };
+                    degrees = (degrees + turn) % 360;
+                    for _ in 0..turn / 90 {
+                        (we, wn) = (wn, -we);
+                    }
+                }
+                "F" if waypoint => {
+                    east += we * n;
+                    north += wn * n;
+                }
+                "F" => match degrees {
+                    0 => north += n,
+                    90 => east += n,
+                    180 => north -= n,
+                    _ => east -= n,
+                },
+                _ if waypoint => {
+                    we += de;
+                    wn += dn;
+                }
+                _ => {
+                    east += de;
+                    north += dn;
+                }
+            }
+        }
+        east.abs() + north.abs()
+    }
+
+    #[test]
+    fn parse_actions() {
+        assert_eq!(
+            "N3".parse::<Action>().unwrap(),
+            Action::Move(Direction::North, 3)
+        );
+        assert_eq!("L270".parse::<Action>().unwrap(), Action::Turn(-3));
+        assert_eq!("F0".parse::<Action>().unwrap(), Action::Forward(0));
+        assert!("R45".parse::<Action>().is_err());
+        assert!("X10".parse::<Action>().is_err());
+        assert!("F-1".parse::<Action>().is_err());
+    }
+
+    const PAIRS: &[&dyn prop::Equivalence] = &[&prop::Pair {
+        name: "day12 navigation",
+        generate: |rng| {
+            let size = rng.range(1, 60);
+            prop::Lines::from(gen::navigation(rng, size))
+        },
+        reference: |lines| {
+            Some((
+                distance_reference(lines, false) as u64,
+                distance_reference(lines, true) as u64,
+            ))
+        },
+        optimised: |lines| {
+            let actions = Day12::parse(&lines.text()).ok()?;
+            Some((
+                distance(&actions, Navigation::Heading),
+                distance(&actions, Navigation::Waypoint),
+            ))
+        },
+    }];
+
+    #[test]
+    fn pairs_agree() {
+        prop::check_all(PAIRS);
+    }
+}
@@ -0,0 +1,160 @@
+use std::ops::Range;
+
+use crate::gen::{self, Rng};
+use adventofcode::num::{self, NumError};
+use adventofcode::parser::{self, Parser};
+use adventofcode::runner::Puzzle;
+use adventofcode::{Answer, Error};
+
+/// Buses in service on real inputs, and the ids they get.
+const BUSES: usize = 9;
+const IDS: Range<usize> = 13..1000;
+
+/// The earliest departure and the bus ids, `None` for `x` slots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    pub earliest: u64,
+    pub buses: Vec<Option<u64>>,
+}
+
+/// The first bus to leave at or after the earliest departure, and how long
+/// it takes to come.
+pub fn first_bus(schedule: &Schedule) -> Option<(u64, u64)> {
+    schedule
+        .buses
+        .iter()
+        .flatten()
+        .map(|&id| (id, (id - schedule.earliest % id) % id))
+        .min_by_key(|&(_, wait)| wait)
+}
+
+/// The first timestamp where every bus leaves as many minutes after it as
+/// its position in the schedule.
+pub fn aligned_timestamp(buses: &[Option<u64>]) -> Result<i128, NumError> {
+    let congruences: Vec<_> = buses
+        .iter()
+        .enumerate()
+        .filter_map(|(offset, id)| id.map(|id| (-(offset as i128), id as i128)))
+        .collect();
+    num::crt(&congruences).map(|(timestamp, _)| timestamp)
+}
+
+pub fn solve_part1(input: &str) -> Result<Option<u64>, Error> {
+    Ok(first_bus(&Day13::parse(input)?).map(|(id, wait)| id * wait))
+}
+
+pub fn solve_part2(input: &str) -> Result<i128, Error> {
+    Ok(aligned_timestamp(&Day13::parse(input)?.buses)?)
+}
+
+pub struct Day13;
+
+impl Puzzle for Day13 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 13;
+    type Input = Schedule;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let id = parser::unsigned::<u64>().map_res(|id| match id {
+            0 => Err(String::from("a bus id above 0")),
+            id => Ok(Some(id)),
+        });
+        let bus = id.or(parser::char('x').map(|_| None));
+        let schedule = parser::unsigned::<u64>()
+            .skip(parser::char('\n'))
+            .and(bus.sep_by(parser::char(',')));
+        let (earliest, buses) = parser::parse_all(&schedule, input.trim_end())?;
+        Ok(Schedule { earliest, buses })
+    }
+
+    fn part1(schedule: &Self::Input) -> Option<Answer> {
+        first_bus(schedule).map(|(id, wait)| Answer::from(id * wait))
+    }
+
+    fn part2(schedule: &Self::Input) -> Option<Answer> {
+        Self::try_part2(schedule).ok().flatten()
+    }
+
+    /// Reports ids sharing a factor, or a timestamp too large for `i128`,
+    /// rather than showing no answer.
+    fn try_part2(schedule: &Self::Input) -> Result<Option<Answer>, Error> {
+        Ok(Some(Answer::from(aligned_timestamp(&schedule.buses)?)))
+    }
+
+    fn generate(rng: &mut Rng, options: &gen::Options) -> Option<String> {
+        let buses = (options.size / 20).clamp(1, BUSES);
+        Some(gen::bus_schedule(rng, buses, IDS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adventofcode::prop;
+
+    /// Waits minute by minute, then tries every timestamp in turn.
+    fn reference(lines: &[String]) -> Option<(u64, u64)> {
+        let earliest: u64 = lines.first()?.parse().ok()?;
+        let buses: Vec<(u64, u64)> = lines[1..]
+            .iter()
+            .enumerate()
+            .filter_map(|(offset, id)| Some((offset as u64, id.parse().ok()?)))
+            .collect();
+        if buses.is_empty() {
+            return None;
+        }
+        let limit: u64 = buses.iter().map(|&(_, id)| id).product();
+        let product = (earliest..).find_map(|t| {
+            let (_, id) = buses.iter().find(|&&(_, id)| t % id == 0)?;
+            Some(id * (t - earliest))
+        })?;
+        let aligned =
+            (0..limit).find(|t| buses.iter().all(|(offset, id)| (t + offset) % id == 0))?;
+        Some((product, aligned))
+    }
+
+    #[test]
+    fn example_offsets() {
+        let aligned = |line: &str| {
+            let input = format!("0\n{}", line);
+            aligned_timestamp(&Day13::parse(&input).unwrap().buses)
+        };
+        assert_eq!(aligned("17,x,13,19"), Ok(3417));
+        assert_eq!(aligned("67,7,59,61"), Ok(754018));
+        assert_eq!(aligned("1789,37,47,1889"), Ok(1202161486));
+        assert_eq!(aligned("4,x,6"), Err(NumError::NotCoprime(4, 6)));
+        let schedule = Day13::parse("939\n4,x,6").unwrap();
+        let err = Day13::try_part2(&schedule).unwrap_err();
+        assert!(
+            matches!(err, Error::Num(NumError::NotCoprime(4, 6))),
+            "{:?}",
+            err
+        );
+        assert!(Day13::parse("939\n7,0,x").is_err());
+    }
+
+    const PAIRS: &[&dyn prop::Equivalence] = &[&prop::Pair {
+        name: "day13 buses",
+        generate: |rng| {
+            let buses = rng.range(1, 4);
+            let text = gen::bus_schedule(rng, buses, 2..24);
+            let (earliest, slots) = text.trim_end().split_once('\n').unwrap();
+            let mut lines = vec![String::from(earliest)];
+            lines.extend(slots.split(',').map(String::from));
+            lines
+        },
+        reference: |lines| reference(lines),
+        optimised: |lines| {
+            let input = format!("{}\n{}", lines.first()?, lines[1..].join(","));
+            let schedule = Day13::parse(&input).ok()?;
+            let (id, wait) = first_bus(&schedule)?;
+            let aligned = aligned_timestamp(&schedule.buses).ok()?;
+            Some((id * wait, aligned as u64))
+        },
+    }];
+
+    #[test]
+    fn pairs_agree() {
+        prop::check_all(PAIRS);
+    }
+}
@@ -0,0 +1,208 @@
+use std::str::FromStr;
+
+use crate::gen::{self, Rng};
+use adventofcode::bits::{Mask, Memory};
+use adventofcode::parser::{self, Parser};
+use adventofcode::runner::Puzzle;
+use adventofcode::{fs, Answer, Error};
+
+/// Floating bits a mask may have before the address decoder gives up, as
+/// each one doubles the writes. Real inputs have at most nine.
+const MAX_FLOATING: u32 = 16;
+
+/// `mask = XX1X0` or `mem[8] = 11`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Mask(Mask),
+    Write { address: u64, value: u64 },
+}
+
+impl FromStr for Instruction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mask = parser::tag("mask = ")
+            .then(parser::rest())
+            .map_res(|mask| {
+                mask.parse()
+                    .map_err(|_| String::from("a mask of 0, 1 and X"))
+            })
+            .map(Instruction::Mask);
+        let write = parser::tag("mem[")
+            .then(parser::unsigned::<u64>())
+            .skip(parser::tag("] = "))
+            .and(parser::unsigned::<u64>())
+            .map(|(address, value)| Instruction::Write { address, value });
+        Ok(parser::parse_all(&mask.or(write), s)?)
+    }
+}
+
+/// How the current mask acts on each write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decoder {
+    /// The mask overwrites bits of the value.
+    Value,
+    /// The mask turns the address into every address it floats over.
+    Address,
+}
+
+/// Memory after running `program`, unless a mask floats over too many bits
+/// for the address decoder.
+pub fn run(program: &[Instruction], decoder: Decoder) -> Option<Memory> {
+    let mut mask = Mask::default();
+    let mut memory = Memory::new();
+    for &instruction in program {
+        match (instruction, decoder) {
+            (Instruction::Mask(m), _) => mask = m,
+            (Instruction::Write { address, value }, Decoder::Value) => {
+                memory.write(address, mask.apply(value))
+            }
+            (Instruction::Write { address, value }, Decoder::Address) => {
+                if mask.floating_count() > MAX_FLOATING {
+                    return None;
+                }
+                for address in mask.addresses(address) {
+                    memory.write(address, value);
+                }
+            }
+        }
+    }
+    Some(memory)
+}
+
+/// Sum of every value left in memory.
+pub fn memory_sum(program: &[Instruction], decoder: Decoder) -> Option<u128> {
+    run(program, decoder).map(|memory| memory.sum())
+}
+
+pub fn solve_part1(input: &str) -> Result<Option<u128>, Error> {
+    Ok(memory_sum(&Day14::parse(input)?, Decoder::Value))
+}
+
+pub fn solve_part2(input: &str) -> Result<Option<u128>, Error> {
+    Ok(memory_sum(&Day14::parse(input)?, Decoder::Address))
+}
+
+pub struct Day14;
+
+impl Puzzle for Day14 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 14;
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let program: Vec<Instruction> = fs::parse_str(input, "\n")?;
+        match program.first() {
+            Some(Instruction::Mask(_)) => Ok(program),
+            _ => Err(Error::Parse(String::from("program must start with a mask"))),
+        }
+    }
+
+    fn part1(program: &Self::Input) -> Option<Answer> {
+        memory_sum(program, Decoder::Value).map(Answer::from)
+    }
+
+    fn part2(program: &Self::Input) -> Option<Answer> {
+        memory_sum(program, Decoder::Address).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, options: &gen::Options) -> Option<String> {
+        Some(gen::docking_program(rng, options.size, 9))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adventofcode::prop;
+    use std::collections::HashMap;
+
+    /// Every address a mask string floats `address` over, one `X` at a time.
+    fn expand(mask: &[u8], address: u64) -> Vec<u64> {
+        let mut addresses = vec![0];
+        for (i, &c) in mask.iter().enumerate() {
+            let bit = 35 - i;
+            addresses = addresses
+                .into_iter()
+                .flat_map(|a| match c {
+                    b'0' => vec![a | (address & 1 << bit)],
+                    b'1' => vec![a | 1 << bit],
+                    _ => vec![a, a | 1 << bit],
+                })
+                .collect();
+        }
+        addresses
+    }
+
+    /// Applies the mask string character by character.
+    fn sums_reference(lines: &[String]) -> Option<(u128, u128)> {
+        let mut mask = lines.first()?.strip_prefix("mask = ")?.as_bytes();
+        let (mut values, mut floating) = (HashMap::new(), HashMap::new());
+        for line in lines {
+            if let Some(m) = line.strip_prefix("mask = ") {
+                mask = m.as_bytes();
+                continue;
+            }
+            let (address, value) = line.strip_prefix("mem[")?.split_once("] = ")?;
+            let (address, value): (u64, u64) = (address.parse().ok()?, value.parse().ok()?);
+            let mut masked = value;
+            for (i, &c) in mask.iter().enumerate() {
+                let bit = 35 - i;
+                match c {
+                    b'0' => masked &= !(1 << bit),
+                    b'1' => masked |= 1 << bit,
+                    _ => {}
+                }
+            }
+            values.insert(address, masked);
+            for a in expand(mask, address) {
+                floating.insert(a, value);
+            }
+        }
+        let sum = |m: HashMap<u64, u64>| m.values().map(|&v| v as u128).sum();
+        Some((sum(values), sum(floating)))
+    }
+
+    #[test]
+    fn parse_program() {
+        let program = Day14::parse("mask = X1X0\nmem[8] = 11\n").unwrap();
+        assert_eq!(
+            program[1],
+            Instruction::Write {
+                address: 8,
+                value: 11
+            }
+        );
+        assert!(Day14::parse("mem[8] = 11\nmask = X1X0\n").is_err());
+        assert!(Day14::parse("mask = X1Y0\n").is_err());
+        assert!(Day14::parse("mask = X1X0\nmem[8] = -1\n").is_err());
+    }
+
+    #[test]
+    fn too_many_floating_bits() {
+        let program = Day14::parse(&format!("mask = {}\nmem[8] = 11\n", "X".repeat(36))).unwrap();
+        assert_eq!(memory_sum(&program, Decoder::Value), Some(11));
+        assert_eq!(memory_sum(&program, Decoder::Address), None);
+    }
+
+    const PAIRS: &[&dyn prop::Equivalence] = &[&prop::Pair {
+        name: "day14 docking",
+        generate: |rng| {
+            let size = rng.range(2, 40);
+            prop::Lines::from(gen::docking_program(rng, size, 6))
+        },
+        reference: |lines| sums_reference(lines),
+        optimised: |lines| {
+            let program = Day14::parse(&lines.text()).ok()?;
+            Some((
+                memory_sum(&program, Decoder::Value)?,
+                memory_sum(&program, Decoder::Address)?,
+            ))
+        },
+    }];
+
+    #[test]
+    fn pairs_agree() {
+        prop::check_all(PAIRS);
+    }
+}
@@ -0,0 +1,73 @@
+use crate::gen::{self, Rng};
+use adventofcode::runner::Puzzle;
+use adventofcode::sequence::MemoryGame;
+use adventofcode::{fs, Answer, Error};
+
+/// The number spoken on `turn` of the game started with `starting`.
+pub fn spoken_on(starting: &[u32], turn: u32) -> Option<u32> {
+    MemoryGame::new(starting).play_until(turn)
+}
+
+pub fn solve_part1(input: &str) -> Result<Option<u32>, Error> {
+    Ok(spoken_on(&Day15::parse(input)?, 2020))
+}
+
+pub fn solve_part2(input: &str) -> Result<Option<u32>, Error> {
+    Ok(spoken_on(&Day15::parse(input)?, 30_000_000))
+}
+
+pub struct Day15;
+
+impl Puzzle for Day15 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 15;
+    type Input = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        fs::parse_str(input.trim(), ",")
+    }
+
+    fn part1(starting: &Self::Input) -> Option<Answer> {
+        spoken_on(starting, 2020).map(Answer::from)
+    }
+
+    fn part2(starting: &Self::Input) -> Option<Answer> {
+        spoken_on(starting, 30_000_000).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, options: &gen::Options) -> Option<String> {
+        Some(gen::starting_numbers(rng, options.size / 30))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adventofcode::prop;
+
+    /// Looks back through everything spoken so far on every turn.
+    fn spoken_reference(starting: &[u32], turn: usize) -> Option<u32> {
+        let mut spoken = starting.to_vec();
+        while spoken.len() < turn {
+            let (&last, before) = spoken.split_last()?;
+            let age = before.iter().rev().position(|&v| v == last);
+            spoken.push(age.map_or(0, |age| age as u32 + 1));
+        }
+        spoken.get(turn.checked_sub(1)?).copied()
+    }
+
+    const PAIRS: &[&dyn prop::Equivalence] = &[&prop::Pair {
+        name: "day15 memory game",
+        generate: |rng| {
+            let count = rng.range(1, 7);
+            Day15::parse(&gen::starting_numbers(rng, count)).unwrap()
+        },
+        reference: |starting| spoken_reference(starting, 2020),
+        optimised: |starting| spoken_on(starting, 2020),
+    }];
+
+    #[test]
+    fn pairs_agree() {
+        prop::check_all(PAIRS);
+    }
+}
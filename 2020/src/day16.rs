@@ -0,0 +1,316 @@
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+use crate::gen::{self, Rng};
+use adventofcode::constraint::{self, ConstraintError};
+use adventofcode::interval::{Interval, IntervalSet};
+use adventofcode::parser::{self, Parser};
+use adventofcode::runner::Puzzle;
+use adventofcode::{fs, Answer, Error};
+
+/// `departure location: 25-80 or 90-961`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    pub name: String,
+    pub valid: IntervalSet<u64>,
+}
+
+impl FromStr for Rule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = parser::take_while1("a field name", |c| c != ':');
+        let interval = parser::unsigned::<u64>()
+            .skip(parser::char('-'))
+            .and(parser::unsigned::<u64>())
+            .map_res(|(lo, hi)| match Interval::new(lo, hi) {
+                i if i.is_empty() => Err(format!("a range, not {}", i)),
+                i => Ok(i),
+            });
+        let rule = name
+            .skip(parser::tag(": "))
+            .and(interval.sep_by(parser::tag(" or ")));
+        let (name, intervals) = parser::parse_all(&rule, s)?;
+        Ok(Rule {
+            name: String::from(name),
+            valid: intervals.into_iter().collect(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notes {
+    pub rules: Vec<Rule>,
+    pub yours: Vec<u64>,
+    pub nearby: Vec<Vec<u64>>,
+}
+
+impl Notes {
+    /// Values valid for at least one field.
+    pub fn valid_anywhere(&self) -> IntervalSet<u64> {
+        self.rules
+            .iter()
+            .fold(IntervalSet::new(), |all, rule| all.union(&rule.valid))
+    }
+}
+
+/// Sum of the nearby ticket values that fit no field at all.
+pub fn error_rate(notes: &Notes) -> u64 {
+    let valid = notes.valid_anywhere();
+    notes
+        .nearby
+        .iter()
+        .flatten()
+        .filter(|&&v| !valid.contains(v))
+        .sum()
+}
+
+/// The column of each field, deduced from your ticket and the nearby tickets
+/// whose values all fit some field.
+pub fn field_columns(notes: &Notes) -> Result<Vec<usize>, ConstraintError> {
+    let valid = notes.valid_anywhere();
+    let tickets: Vec<&Vec<u64>> = notes
+        .nearby
+        .iter()
+        .chain([&notes.yours])
+        .filter(|t| t.iter().all(|&v| valid.contains(v)))
+        .collect();
+    let candidates = notes
+        .rules
+        .iter()
+        .map(|rule| {
+            (0..notes.yours.len())
+                .filter(|&col| tickets.iter().all(|t| rule.valid.contains(t[col])))
+                .collect::<BTreeSet<_>>()
+        })
+        .collect();
+    constraint::eliminate(candidates)
+}
+
+/// Product of your values for the fields starting with `departure`, if
+/// there are such fields, or why the columns could not be told apart.
+pub fn departure_product(notes: &Notes) -> Result<Option<u64>, ConstraintError> {
+    let columns = field_columns(notes)?;
+    let mut values = notes
+        .rules
+        .iter()
+        .zip(columns)
+        .filter(|(rule, _)| rule.name.starts_with("departure"))
+        .map(|(_, col)| notes.yours[col])
+        .peekable();
+    if values.peek().is_none() {
+        return Ok(None);
+    }
+    Ok(values.try_fold(1u64, |product, v| product.checked_mul(v)))
+}
+
+pub fn solve_part1(input: &str) -> Result<u64, Error> {
+    Ok(error_rate(&Day16::parse(input)?))
+}
+
+pub fn solve_part2(input: &str) -> Result<Option<u64>, Error> {
+    Ok(departure_product(&Day16::parse(input)?)?)
+}
+
+fn ticket(line: &str) -> Result<Vec<u64>, Error> {
+    fs::parse_str(line, ",")
+}
+
+/// The lines of `section` after its `heading` line.
+fn under<'a>(heading: &str, section: &'a str) -> Result<&'a str, Error> {
+    match section.split_once('\n').unwrap_or((section, "")) {
+        (first, rest) if first == heading => Ok(rest),
+        _ => Err(Error::Parse(format!("expected `{}`", heading))),
+    }
+}
+
+pub struct Day16;
+
+impl Puzzle for Day16 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 16;
+    type Input = Notes;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let sections: Vec<&str> = input.trim().split("\n\n").collect();
+        let [rules, yours, nearby] = sections[..] else {
+            return Err(Error::Parse(format!(
+                "expected rules, your ticket and nearby tickets, found {} sections",
+                sections.len()
+            )));
+        };
+        let notes = Notes {
+            rules: fs::parse_str(rules, "\n")?,
+            yours: ticket(under("your ticket:", yours)?)?,
+            nearby: under("nearby tickets:", nearby)?
+                .lines()
+                .map(ticket)
+                .collect::<Result<_, _>>()?,
+        };
+        let fields = notes.rules.len();
+        if let Some(t) = [&notes.yours]
+            .into_iter()
+            .chain(&notes.nearby)
+            .find(|t| t.len() != fields)
+        {
+            return Err(Error::Parse(format!(
+                "ticket has {} values for {} fields",
+                t.len(),
+                fields
+            )));
+        }
+        Ok(notes)
+    }
+
+    fn part1(notes: &Self::Input) -> Option<Answer> {
+        Some(Answer::from(error_rate(notes)))
+    }
+
+    fn part2(notes: &Self::Input) -> Option<Answer> {
+        Self::try_part2(notes).ok().flatten()
+    }
+
+    /// Reports columns that cannot be told apart rather than showing no
+    /// answer.
+    fn try_part2(notes: &Self::Input) -> Result<Option<Answer>, Error> {
+        Ok(departure_product(notes)?.map(Answer::from))
+    }
+
+    fn generate(rng: &mut Rng, options: &gen::Options) -> Option<String> {
+        Some(gen::ticket_notes(rng, 20, options.size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adventofcode::prop;
+
+    fn permutations(n: usize) -> Vec<Vec<usize>> {
+        if n == 0 {
+            return vec![Vec::new()];
+        }
+        let mut out = Vec::new();
+        for p in permutations(n - 1) {
+            for at in 0..=p.len() {
+                let mut p = p.clone();
+                p.insert(at, n - 1);
+                out.push(p);
+            }
+        }
+        out
+    }
+
+    /// Parses with string splits, checks every range of every rule directly
+    /// and tries every assignment of fields to columns.
+    fn reference(lines: &[String]) -> Option<(u64, Option<u64>)> {
+        let blank = lines.iter().position(|l| l.is_empty())?;
+        let (rule_lines, rest) = lines.split_at(blank);
+        let headings = [rest.get(1)?, rest.get(3)?, rest.get(4)?];
+        if headings != ["your ticket:", "", "nearby tickets:"] {
+            return None;
+        }
+        let mut rules = Vec::new();
+        for line in rule_lines {
+            let (name, ranges) = line.split_once(": ")?;
+            let mut bounds = Vec::new();
+            for range in ranges.split(" or ") {
+                let (lo, hi) = range.split_once('-')?;
+                let (lo, hi): (u64, u64) = (lo.parse().ok()?, hi.parse().ok()?);
+                if lo > hi {
+                    return None;
+                }
+                bounds.push((lo, hi));
+            }
+            rules.push((name, bounds));
+        }
+        let ticket = |line: &String| -> Option<Vec<u64>> {
+            let values: Option<Vec<u64>> = line.split(',').map(|v| v.parse().ok()).collect();
+            values.filter(|v| v.len() == rules.len())
+        };
+        let yours = ticket(rest.get(2)?)?;
+        let nearby: Vec<Vec<u64>> = rest[5..].iter().map(ticket).collect::<Option<_>>()?;
+        let fits = |r: usize, v: u64| rules[r].1.iter().any(|&(lo, hi)| lo <= v && v <= hi);
+        let valid = |v: u64| (0..rules.len()).any(|r| fits(r, v));
+
+        let rate = nearby.iter().flatten().filter(|&&v| !valid(v)).sum();
+        let mut tickets: Vec<&Vec<u64>> = nearby
+            .iter()
+            .filter(|t| t.iter().all(|&v| valid(v)))
+            .collect();
+        tickets.push(&yours);
+        let orders: Vec<Vec<usize>> = permutations(rules.len())
+            .into_iter()
+            .filter(|cols| {
+                cols.iter()
+                    .enumerate()
+                    .all(|(r, &c)| tickets.iter().all(|t| fits(r, t[c])))
+            })
+            .collect();
+        let product = match &orders[..] {
+            [cols] => {
+                let departures: Vec<u64> = (0..rules.len())
+                    .filter(|&r| rules[r].0.starts_with("departure"))
+                    .map(|r| yours[cols[r]])
+                    .collect();
+                (!departures.is_empty()).then(|| departures.iter().product())
+            }
+            _ => None,
+        };
+        Some((rate, product))
+    }
+
+    #[test]
+    fn parse_notes() {
+        let rule: Rule = "arrival track: 5-9 or 1-4".parse().unwrap();
+        assert_eq!(rule.name, "arrival track");
+        assert_eq!(rule.valid.intervals(), [Interval::new(1, 9)]);
+        assert!("seat: 5-3 or 7-9".parse::<Rule>().is_err());
+        assert!("seat 1-3".parse::<Rule>().is_err());
+
+        let notes = "a: 1-3\nb: 5-7\n\nyour ticket:\n1,5\n\nnearby tickets:\n";
+        assert_eq!(Day16::parse(notes).unwrap().nearby.len(), 0);
+        let short = "a: 1-3\nb: 5-7\n\nyour ticket:\n1,5\n\nnearby tickets:\n3\n";
+        assert!(Day16::parse(short).is_err());
+        assert!(Day16::parse("a: 1-3\n\nnearby tickets:\n3\n").is_err());
+    }
+
+    #[test]
+    fn ambiguous_columns() {
+        let notes =
+            "departure a: 1-9\ndeparture b: 1-9\n\nyour ticket:\n2,3\n\nnearby tickets:\n4,5\n";
+        let notes = Day16::parse(notes).unwrap();
+        assert_eq!(
+            field_columns(&notes),
+            Err(ConstraintError::Ambiguous(vec![0, 1]))
+        );
+        assert_eq!(
+            departure_product(&notes),
+            Err(ConstraintError::Ambiguous(vec![0, 1]))
+        );
+        let err = Day16::try_part2(&notes).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "items [0, 1] could each take several values"
+        );
+        assert_eq!(Day16::part2(&notes), None);
+    }
+
+    const PAIRS: &[&dyn prop::Equivalence] = &[&prop::Pair {
+        name: "day16 tickets",
+        generate: |rng| {
+            let (fields, tickets) = (rng.range(1, 6), rng.range(0, 20));
+            prop::Lines::from(gen::ticket_notes(rng, fields, tickets))
+        },
+        reference: |lines| reference(lines),
+        optimised: |lines| {
+            let notes = Day16::parse(&lines.text()).ok()?;
+            Some((error_rate(&notes), departure_product(&notes).ok().flatten()))
+        },
+    }];
+
+    #[test]
+    fn pairs_agree() {
+        prop::check_all(PAIRS);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::gen::{self, Rng};
+use adventofcode::automaton::SparseAutomaton;
+use adventofcode::grid::Grid;
+use adventofcode::runner::Puzzle;
+use adventofcode::{Answer, Error};
+
+/// Cycles of the boot process.
+const CYCLES: usize = 6;
+
+/// Active cubes stay active with two or three active neighbours; inactive
+/// ones turn active with exactly three.
+fn rule(active: bool, neighbours: usize) -> bool {
+    matches!((active, neighbours), (true, 2) | (_, 3))
+}
+
+/// Active cubes after the boot process in `D` dimensions.
+pub fn boot<const D: usize>(slice: &Grid<bool>) -> usize {
+    let mut space = SparseAutomaton::<D>::from_grid(slice);
+    for _ in 0..CYCLES {
+        space.step(rule);
+    }
+    space.len()
+}
+
+pub fn solve_part1(input: &str) -> Result<usize, Error> {
+    Ok(boot::<3>(&Day17::parse(input)?))
+}
+
+pub fn solve_part2(input: &str) -> Result<usize, Error> {
+    Ok(boot::<4>(&Day17::parse(input)?))
+}
+
+pub struct Day17;
+
+impl Puzzle for Day17 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 17;
+    type Input = Grid<bool>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Grid::parse(input, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+    }
+
+    fn part1(slice: &Self::Input) -> Option<Answer> {
+        Some(Answer::from(boot::<3>(slice)))
+    }
+
+    fn part2(slice: &Self::Input) -> Option<Answer> {
+        Some(Answer::from(boot::<4>(slice)))
+    }
+
+    fn generate(rng: &mut Rng, options: &gen::Options) -> Option<String> {
+        Some(gen::cube_slice(rng, options.width))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adventofcode::prop;
+
+    /// Three-dimensional cycles in a dense box padded by one cube per cycle.
+    fn boot_reference(rows: &[String], cycles: usize) -> usize {
+        let (h, w) = (rows.len(), rows[0].len());
+        let pad = cycles + 1;
+        let (nx, ny, nz) = (w + 2 * pad, h + 2 * pad, 1 + 2 * pad);
+        let mut cubes = vec![vec![vec![false; nx]; ny]; nz];
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.bytes().enumerate() {
+                cubes[pad][y + pad][x + pad] = c == b'#';
+            }
+        }
+        for _ in 0..cycles {
+            let mut next = cubes.clone();
+            for z in 1..nz - 1 {
+                for y in 1..ny - 1 {
+                    for x in 1..nx - 1 {
+                        let count = cubes[z - 1..=z + 1]
+                            .iter()
+                            .flat_map(|plane| &plane[y - 1..=y + 1])
+                            .flat_map(|row| &row[x - 1..=x + 1])
+                            .filter(|&&c| c)
+                            .count();
+                        let active = cubes[z][y][x];
+                        next[z][y][x] = rule(active, count - active as usize);
+                    }
+                }
+            }
+            cubes = next;
+        }
+        cubes.iter().flatten().flatten().filter(|&&c| c).count()
+    }
+
+    #[test]
+    fn flat_space() {
+        let glider = Day17::parse(".#.\n..#\n###\n").unwrap();
+        let mut plane = SparseAutomaton::<2>::from_grid(&glider);
+        for _ in 0..4 {
+            plane.step(rule);
+        }
+        // A glider moves one step diagonally every four generations.
+        assert_eq!(plane.len(), 5);
+        assert!(plane.is_active(&[2, 1]) && plane.is_active(&[3, 2]));
+    }
+
+    const PAIRS: &[&dyn prop::Equivalence] = &[&prop::Pair {
+        name: "day17 cubes",
+        generate: |rng| {
+            let side = rng.range(1, 4);
+            prop::Lines::from(gen::cube_slice(rng, side))
+        },
+        reference: |rows| {
+            let ok = !rows.is_empty() && rows.iter().all(|r| r.len() == rows[0].len());
+            ok.then(|| boot_reference(rows, 3))
+        },
+        optimised: |rows| {
+            let slice = Day17::parse(&rows.text()).ok()?;
+            let mut space = SparseAutomaton::<3>::from_grid(&slice);
+            for _ in 0..3 {
+                space.step(rule);
+            }
+            Some(space.len())
+        },
+    }];
+
+    #[test]
+    fn pairs_agree() {
+        prop::check_all(PAIRS);
+    }
+}
@@ -0,0 +1,636 @@
+//! Seeded random input generators for stress-testing the 2020 solutions.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::ops::Range;
+
+pub use adventofcode::gen::{Options, Rng};
+use adventofcode::sums;
+use adventofcode::vm::{self, Instruction, Machine, Op};
+
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const TARGET: usize = 2020;
+
+fn completes_sum(values: &[usize], set: &HashSet<usize>, c: usize) -> bool {
+    if c >= TARGET {
+        return false;
+    }
+    let rest = TARGET - c;
+    set.contains(&rest)
+        || values
+            .iter()
+            .any(|&v| v < rest && set.contains(&(rest - v)))
+}
+
+fn count_sums(values: &[usize]) -> (usize, usize) {
+    let n = values.len();
+    let mut pairs = 0;
+    let mut trits = 0;
+    for i in 0..n {
+        for j in i + 1..n {
+            pairs += (values[i] + values[j] == TARGET) as usize;
+            for k in j + 1..n {
+                trits += (values[i] + values[j] + values[k] == TARGET) as usize;
+            }
+        }
+    }
+    (pairs, trits)
+}
+
+/// Expense report with exactly one pair and one triple summing to 2020.
+///
+/// Apart from the planted entries every number is above 1010, so no two of
+/// them can reach 2020 together; candidates that would complete a sum with
+/// the planted entries are rejected.
+pub fn expense_report(rng: &mut Rng, size: usize) -> String {
+    let mut values = loop {
+        let a = rng.range(1, 1009);
+        let x = rng.range(1, 672);
+        let y = rng.range(x + 1, 673);
+        let planted = vec![a, TARGET - a, x, y, TARGET - x - y];
+        if count_sums(&planted) == (1, 1) {
+            break planted;
+        }
+    };
+
+    let mut set: HashSet<usize> = values.iter().copied().collect();
+    while values.len() < size {
+        let c = rng.range(1011, 2019);
+        if !completes_sum(&values, &set, c) {
+            values.push(c);
+            set.insert(c);
+        }
+    }
+    rng.shuffle(&mut values);
+    join_lines(values.iter())
+}
+
+/// Password policy lines like `1-3 a: abcde`.
+pub fn passwords(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let len = rng.range(1, 20);
+        let hi = rng.range(1, len);
+        let lo = rng.range(1, hi);
+        let chr = *rng.pick(LOWERCASE) as char;
+        let mut pwd = rng.letters(LOWERCASE, len);
+        if rng.chance(0.5) {
+            // Make both policies interesting rather than almost always false.
+            pwd.replace_range(lo - 1..lo, &chr.to_string());
+        }
+        let _ = writeln!(out, "{}-{} {}: {}", lo, hi, chr, pwd);
+    }
+    out
+}
+
+/// A grid of `size` rows and `width` columns where each cell is `hit` with
+/// probability `p` and `miss` otherwise.
+fn scatter(rng: &mut Rng, size: usize, width: usize, p: f64, hit: char, miss: char) -> String {
+    let mut out = String::with_capacity(size * (width + 1));
+    for _ in 0..size.max(1) {
+        for _ in 0..width.max(1) {
+            out.push(if rng.chance(p) { hit } else { miss });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Tree map of `size` rows and `width` columns with roughly one tree in five.
+pub fn tree_grid(rng: &mut Rng, size: usize, width: usize) -> String {
+    scatter(rng, size, width, 0.2, '#', '.')
+}
+
+const EYE_COLORS: &[&str] = &["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+const REQUIRED_FIELDS: &[&str] = &["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+
+fn passport_value(rng: &mut Rng, key: &str, valid: bool) -> String {
+    match (key, valid) {
+        ("byr", true) => rng.range(1920, 2002).to_string(),
+        ("byr", false) if rng.chance(0.5) => rng.range(1900, 1919).to_string(),
+        ("byr", false) => rng.range(2003, 2030).to_string(),
+        ("iyr", true) => rng.range(2010, 2020).to_string(),
+        ("iyr", false) => rng.range(1990, 2009).to_string(),
+        ("eyr", true) => rng.range(2020, 2030).to_string(),
+        ("eyr", false) => rng.range(2031, 2040).to_string(),
+        ("hgt", true) if rng.chance(0.5) => format!("{}cm", rng.range(150, 193)),
+        ("hgt", true) => format!("{}in", rng.range(59, 76)),
+        ("hgt", false) => match rng.range(0, 2) {
+            0 => format!("{}cm", rng.range(194, 250)),
+            1 => format!("{}in", rng.range(20, 58)),
+            _ => rng.range(59, 193).to_string(),
+        },
+        ("hcl", true) => format!("#{}", rng.letters(b"0123456789abcdef", 6)),
+        ("hcl", false) => rng.letters(b"0123456789abcdef", 6),
+        ("ecl", true) => rng.pick(EYE_COLORS).to_string(),
+        ("ecl", false) => rng.pick(&["zzz", "gmt", "xry"]).to_string(),
+        ("pid", true) => rng.letters(b"0123456789", 9),
+        ("pid", false) => {
+            let len = rng.range(10, 12);
+            rng.letters(b"0123456789", len)
+        }
+        _ => rng.range(1, 350).to_string(),
+    }
+}
+
+/// Passports where about `valid` of them pass every field check; the rest
+/// either miss a required field or carry one out-of-range value.
+pub fn passports(rng: &mut Rng, size: usize, valid: f64) -> String {
+    let mut records = Vec::with_capacity(size);
+    for _ in 0..size {
+        let mut keys: Vec<&str> = REQUIRED_FIELDS.to_vec();
+        if rng.chance(0.5) {
+            keys.push("cid");
+        }
+        let mut broken = None;
+        if !rng.chance(valid) {
+            let i = rng.range(0, REQUIRED_FIELDS.len() - 1);
+            if rng.chance(0.5) {
+                keys.retain(|&k| k != REQUIRED_FIELDS[i]);
+            } else {
+                broken = Some(REQUIRED_FIELDS[i]);
+            }
+        }
+        rng.shuffle(&mut keys);
+
+        let mut record = String::new();
+        for (i, key) in keys.iter().enumerate() {
+            if i > 0 {
+                record.push(if rng.chance(0.3) { '\n' } else { ' ' });
+            }
+            let value = passport_value(rng, key, broken != Some(*key));
+            let _ = write!(record, "{}:{}", key, value);
+        }
+        records.push(record);
+    }
+    records.join("\n\n") + "\n"
+}
+
+fn seat_code(id: usize) -> String {
+    let row = (0..7)
+        .rev()
+        .map(|b| if (id >> (b + 3)) & 1 == 1 { 'B' } else { 'F' });
+    let col = (0..3)
+        .rev()
+        .map(|b| if (id >> b) & 1 == 1 { 'R' } else { 'L' });
+    row.chain(col).collect()
+}
+
+/// Up to `size` boarding passes forming one contiguous block of seat ids
+/// with a single missing seat somewhere inside it.
+pub fn boarding_passes(rng: &mut Rng, size: usize) -> String {
+    let count = size.clamp(3, 1000);
+    let first = rng.range(8, 1024 - 8 - count);
+    let gap = rng.range(first + 1, first + count - 2);
+    let mut ids: Vec<usize> = (first..first + count).filter(|&id| id != gap).collect();
+    rng.shuffle(&mut ids);
+    join_lines(ids.into_iter().map(seat_code))
+}
+
+/// Customs declaration groups of one to five people.
+pub fn questionnaires(rng: &mut Rng, size: usize) -> String {
+    let mut groups = Vec::with_capacity(size);
+    for _ in 0..size {
+        let people = rng.range(1, 5);
+        let len = rng.range(0, 3);
+        let common = rng.letters(LOWERCASE, len);
+        let answers: Vec<String> = (0..people)
+            .map(|_| {
+                let mut person: Vec<u8> = common.bytes().collect();
+                let len = rng.range(0, 6);
+                person.extend(rng.letters(LOWERCASE, len).bytes());
+                if person.is_empty() {
+                    person.push(*rng.pick(LOWERCASE));
+                }
+                person.sort_unstable();
+                person.dedup();
+                rng.shuffle(&mut person);
+                String::from_utf8(person).expect("ascii letters")
+            })
+            .collect();
+        groups.push(answers.join("\n"));
+    }
+    groups.join("\n\n") + "\n"
+}
+
+const SHADES: &[&str] = &[
+    "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light", "mirrored",
+    "muted", "pale", "plaid", "posh", "shiny", "striped", "vibrant", "wavy",
+];
+const COLOURS: &[&str] = &[
+    "aqua", "beige", "black", "blue", "bronze", "brown", "coral", "crimson", "cyan", "gold",
+    "gray", "green", "indigo", "lime", "magenta", "olive", "orange", "plum", "red", "silver",
+    "tan", "teal", "violet", "white", "yellow",
+];
+
+/// Nesting rules for `size` bag colours with `shiny gold` among the middle
+/// third. Bags only contain bags listed after them in a hidden order, so the
+/// rules never form a cycle, and no bag holds more than a billion others.
+pub fn bag_rules(rng: &mut Rng, size: usize) -> String {
+    const LIMIT: u64 = 1_000_000_000;
+    let mut names: Vec<String> = SHADES
+        .iter()
+        .flat_map(|s| COLOURS.iter().map(move |c| format!("{} {}", s, c)))
+        .collect();
+    rng.shuffle(&mut names);
+    let count = size.clamp(1, names.len());
+    let gold = names
+        .iter()
+        .position(|n| n == "shiny gold")
+        .expect("planted colour");
+    let slot = rng.range(count / 3, count * 2 / 3);
+    names.swap(gold, slot);
+    names.truncate(count);
+
+    let mut totals = vec![0u64; count];
+    let mut rules = Vec::with_capacity(count);
+    for bag in (0..count).rev() {
+        let mut contents = Vec::new();
+        if bag + 1 < count && rng.chance(0.8) {
+            for _ in 0..rng.range(1, 4) {
+                let inner = rng.range(bag + 1, count - 1);
+                let amount = rng.range(1, 5) as u64;
+                let total = totals[bag] + amount * (totals[inner] + 1);
+                if contents.iter().any(|&(_, i)| i == inner) || total > LIMIT {
+                    continue;
+                }
+                totals[bag] = total;
+                contents.push((amount, inner));
+            }
+        }
+        let contents = if contents.is_empty() {
+            String::from("no other bags")
+        } else {
+            let parts: Vec<String> = contents
+                .iter()
+                .map(|&(amount, inner)| {
+                    let plural = if amount == 1 { "" } else { "s" };
+                    format!("{} {} bag{}", amount, names[inner], plural)
+                })
+                .collect();
+            parts.join(", ")
+        };
+        rules.push(format!("{} bags contain {}.", names[bag], contents));
+    }
+    rng.shuffle(&mut rules);
+    join_lines(rules.iter())
+}
+
+/// Boot code of `size` instructions that loops, but terminates once exactly
+/// one of its instructions, a backwards `jmp`, is turned into a `nop`.
+pub fn boot_code(rng: &mut Rng, size: usize) -> String {
+    let count = size.max(4);
+    loop {
+        // Forward jumps only, so this always runs off the end.
+        let mut program: Vec<Instruction> = (0..count)
+            .map(|ip| match rng.range(0, 2) {
+                0 => Instruction {
+                    op: Op::Acc,
+                    arg: rng.range(0, 100) as i64 - 50,
+                },
+                1 => Instruction {
+                    op: Op::Jmp,
+                    arg: rng.range(1, 5.min(count - ip)) as i64,
+                },
+                _ => Instruction {
+                    op: Op::Nop,
+                    arg: -(rng.range(0, ip) as i64),
+                },
+            })
+            .collect();
+        let mut machine = Machine::new(&program);
+        machine.enable_trace();
+        machine.run();
+        let path: Vec<usize> = machine.trace().iter().map(|s| s.ip).collect();
+        if path.len() < 2 {
+            continue;
+        }
+        let at = rng.range(1, path.len() - 1);
+        let (back, ip) = (path[rng.range(0, at - 1)], path[at]);
+        program[ip] = Instruction {
+            op: Op::Jmp,
+            arg: back as i64 - ip as i64,
+        };
+        if vm::repairs(&program).count() == 1 {
+            return join_lines(program.iter());
+        }
+    }
+}
+
+/// XMAS data where every number after the first `preamble` is the sum of
+/// two of the `preamble` numbers before it, except one planted number that
+/// is the sum of a contiguous range of earlier numbers instead. Starts with
+/// a `preamble: N` line.
+pub fn xmas_data(rng: &mut Rng, size: usize, preamble: usize) -> String {
+    let count = size.max(preamble * 2 + 1);
+    'retry: loop {
+        let mut numbers: Vec<u64> = (1..=preamble as u64 * 2).collect();
+        rng.shuffle(&mut numbers);
+        numbers.truncate(preamble);
+        let planted = rng.range(preamble * 2, count - 1);
+        for i in preamble..count {
+            let mut window = numbers[i - preamble..].to_vec();
+            window.sort_unstable();
+            let next = if i == planted {
+                let len = rng.range(2, preamble);
+                let start = rng.range(0, i - len);
+                let sum = numbers[start..start + len].iter().sum();
+                if sums::find_pair(&window, sum).is_some() {
+                    continue 'retry;
+                }
+                sum
+            } else {
+                // Adding small numbers keeps the growth close to the real
+                // inputs, which stay well within 64 bits.
+                let a = rng.range(0, 2);
+                let b = rng.range(a + 1, (a + 3).min(preamble - 1));
+                window[a] + window[b]
+            };
+            numbers.push(next);
+        }
+        return format!("preamble: {}\n{}", preamble, join_lines(numbers.iter()));
+    }
+}
+
+/// Joltage adapters, shuffled, that chain together in steps of 1 or 3 with
+/// at most four 1-jolt steps in a row, as in the real inputs. Arrangements
+/// overflow `u64` from about 200 adapters and `u128` from about 350.
+pub fn adapters(rng: &mut Rng, size: usize) -> String {
+    let mut joltage = 0;
+    let mut run = 0;
+    let mut adapters = Vec::with_capacity(size);
+    for _ in 0..size {
+        if run < 4 && rng.chance(0.7) {
+            joltage += 1;
+            run += 1;
+        } else {
+            joltage += 3;
+            run = 0;
+        }
+        adapters.push(joltage);
+    }
+    rng.shuffle(&mut adapters);
+    join_lines(adapters.iter())
+}
+
+/// Waiting area of `size` rows and `width` columns, all seats empty and
+/// about three positions in ten floor. Packing seats more densely makes most
+/// layouts flip between full and empty forever instead of settling.
+pub fn seat_layout(rng: &mut Rng, size: usize, width: usize) -> String {
+    scatter(rng, size, width, 0.7, 'L', '.')
+}
+
+/// Initial `side` by `side` slice of the pocket dimension, about half of it
+/// active. Real inputs are 8 by 8; the active region grows by two in every
+/// direction per cycle, so large slices get slow in four dimensions.
+pub fn cube_slice(rng: &mut Rng, side: usize) -> String {
+    scatter(rng, side, side, 0.5, '#', '.')
+}
+
+/// Navigation instructions, mostly moves with the odd turn by a multiple
+/// of 90 degrees.
+pub fn navigation(rng: &mut Rng, size: usize) -> String {
+    let lines = (0..size.max(1)).map(|_| {
+        if rng.chance(0.2) {
+            let letter = if rng.chance(0.5) { 'L' } else { 'R' };
+            format!("{}{}", letter, 90 * rng.range(1, 3))
+        } else {
+            let letter = *rng.pick(&['N', 'S', 'E', 'W', 'F']);
+            format!("{}{}", letter, rng.range(1, 100))
+        }
+    });
+    join_lines(lines)
+}
+
+fn primes_below(n: usize) -> Vec<usize> {
+    let mut composite = vec![false; n];
+    (2..n)
+        .filter(|&p| {
+            if composite[p] {
+                return false;
+            }
+            (p * p..n).step_by(p).for_each(|m| composite[m] = true);
+            true
+        })
+        .collect()
+}
+
+/// Earliest departure and a schedule of up to `buses` distinct prime ids
+/// from `ids`, separated by runs of `x`. Distinct primes keep the ids
+/// coprime, so every schedule has an aligned timestamp.
+pub fn bus_schedule(rng: &mut Rng, buses: usize, ids: Range<usize>) -> String {
+    let max_id = ids.end.max(3);
+    let mut ids: Vec<_> = primes_below(max_id)
+        .into_iter()
+        .filter(|&p| p >= ids.start)
+        .collect();
+    if ids.is_empty() {
+        ids.push(2);
+    }
+    rng.shuffle(&mut ids);
+    ids.truncate(buses.max(1));
+    let mut slots = Vec::new();
+    for (i, id) in ids.iter().enumerate() {
+        if i > 0 {
+            slots.extend((0..rng.range(0, 8)).map(|_| String::from("x")));
+        }
+        slots.push(id.to_string());
+    }
+    format!(
+        "{}\n{}\n",
+        rng.range(max_id, 1000 * max_id),
+        slots.join(",")
+    )
+}
+
+/// Initialisation program of about `size` lines: 36-bit masks with up to
+/// `floating` `X` bits, each followed by a few writes to 16-bit addresses.
+pub fn docking_program(rng: &mut Rng, size: usize, floating: usize) -> String {
+    let mut lines = Vec::with_capacity(size);
+    while lines.len() < size.max(2) {
+        let mut mask: Vec<char> = (0..36)
+            .map(|_| if rng.chance(0.5) { '1' } else { '0' })
+            .collect();
+        for _ in 0..rng.range(0, floating) {
+            let at = rng.range(0, 35);
+            mask[at] = 'X';
+        }
+        lines.push(format!("mask = {}", mask.into_iter().collect::<String>()));
+        for _ in 0..rng.range(1, 6) {
+            let (address, value) = (rng.range(0, 0xffff), rng.range(0, (1 << 36) - 1));
+            lines.push(format!("mem[{}] = {}", address, value));
+        }
+    }
+    join_lines(lines.iter())
+}
+
+/// `count` distinct starting numbers for the memory game, below 20 like
+/// real inputs.
+pub fn starting_numbers(rng: &mut Rng, count: usize) -> String {
+    let mut numbers: Vec<usize> = (0..20).collect();
+    rng.shuffle(&mut numbers);
+    numbers.truncate(count.clamp(1, 20));
+    let line: Vec<String> = numbers.iter().map(usize::to_string).collect();
+    format!("{}\n", line.join(","))
+}
+
+const TICKET_FIELDS: &[&str] = &[
+    "departure location",
+    "departure station",
+    "departure platform",
+    "departure track",
+    "departure date",
+    "departure time",
+    "arrival location",
+    "arrival station",
+    "arrival platform",
+    "arrival track",
+    "class",
+    "duration",
+    "price",
+    "route",
+    "row",
+    "seat",
+    "train",
+    "type",
+    "wagon",
+    "zone",
+];
+
+/// Ticket notes for up to twenty `fields` with `tickets` nearby tickets,
+/// about one in five holding a value no field accepts. Columns draw their
+/// values from separate bands, and each field accepts the bands of the
+/// columns ranked at or below its own in a hidden order, so exactly one
+/// assignment of fields to columns fits.
+pub fn ticket_notes(rng: &mut Rng, fields: usize, tickets: usize) -> String {
+    let n = fields.clamp(1, TICKET_FIELDS.len());
+    let stride = 950 / n;
+    let band = |rank: usize| (25 + rank * stride, 20 + (rank + 1) * stride);
+    let mut names = TICKET_FIELDS.to_vec();
+    rng.shuffle(&mut names);
+    let mut ranks: Vec<usize> = (0..n).collect();
+    rng.shuffle(&mut ranks);
+    let mut out = String::new();
+    for (name, &rank) in names.iter().zip(&ranks) {
+        let (lo, hi) = (band(0).0, band(rank).1);
+        if rank == 0 {
+            let mid = rng.range(lo, hi - 1);
+            let _ = writeln!(out, "{}: {}-{} or {}-{}", name, lo, mid, mid + 1, hi);
+        } else {
+            let gap = rng.range(0, rank - 1);
+            let (below, above) = (band(gap).1, band(gap + 1).0);
+            let _ = writeln!(out, "{}: {}-{} or {}-{}", name, lo, below, above, hi);
+        }
+    }
+    // The column holding the values of each rank.
+    let mut columns: Vec<usize> = (0..n).collect();
+    rng.shuffle(&mut columns);
+    let ticket = |rng: &mut Rng| {
+        let mut values = vec![0; n];
+        for (rank, &col) in columns.iter().enumerate() {
+            let (lo, hi) = band(rank);
+            values[col] = rng.range(lo, hi);
+        }
+        values
+    };
+    let line = |values: Vec<usize>| {
+        let values: Vec<String> = values.iter().map(usize::to_string).collect();
+        values.join(",")
+    };
+    let _ = write!(
+        out,
+        "\nyour ticket:\n{}\n\nnearby tickets:\n",
+        line(ticket(rng))
+    );
+    for _ in 0..tickets {
+        let mut values = ticket(rng);
+        if rng.chance(0.2) {
+            let at = rng.range(0, n - 1);
+            values[at] = if rng.chance(0.5) {
+                rng.range(0, 24)
+            } else {
+                rng.range(25 + n * stride, 999)
+            };
+        }
+        let _ = writeln!(out, "{}", line(values));
+    }
+    out
+}
+
+fn join_lines<T: ToString>(items: impl Iterator<Item = T>) -> String {
+    items.fold(String::new(), |mut out, v| {
+        out.push_str(&v.to_string());
+        out.push('\n');
+        out
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded() {
+        assert_eq!(
+            expense_report(&mut Rng::new(7), 50),
+            expense_report(&mut Rng::new(7), 50)
+        );
+        assert_ne!(
+            passwords(&mut Rng::new(7), 50),
+            passwords(&mut Rng::new(8), 50)
+        );
+    }
+
+    #[test]
+    fn planted_expenses() {
+        for seed in 0..20 {
+            let values: Vec<usize> = expense_report(&mut Rng::new(seed), 100)
+                .lines()
+                .map(|l| l.parse().unwrap())
+                .collect();
+            assert_eq!(values.len(), 100);
+            assert_eq!(count_sums(&values), (1, 1), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn single_gap() {
+        let text = boarding_passes(&mut Rng::new(3), 200);
+        let mut ids: Vec<usize> = text
+            .lines()
+            .map(|l| {
+                l.chars()
+                    .fold(0, |id, c| id * 2 + matches!(c, 'B' | 'R') as usize)
+            })
+            .collect();
+        ids.sort_unstable();
+        let gaps = ids.windows(2).filter(|w| w[1] != w[0] + 1).count();
+        assert_eq!(gaps, 1);
+        assert_eq!(ids.len(), 199);
+    }
+
+    #[test]
+    fn bag_rules_plant_gold() {
+        let text = bag_rules(&mut Rng::new(5), 40);
+        assert_eq!(text.lines().count(), 40);
+        assert_eq!(text.matches("shiny gold bags contain").count(), 1);
+        assert!(text.lines().all(|l| l.ends_with('.')));
+    }
+
+    #[test]
+    fn boot_code_has_one_repair() {
+        for seed in 0..10 {
+            let text = boot_code(&mut Rng::new(seed), 200);
+            let program = vm::parse_program(&text).unwrap();
+            assert_eq!(program.len(), 200);
+            assert_eq!(vm::repairs(&program).count(), 1, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn grid_shape() {
+        let grid = tree_grid(&mut Rng::new(1), 5, 13);
+        assert_eq!(grid.lines().count(), 5);
+        assert!(grid.lines().all(|l| l.len() == 13));
+    }
+}
@@ -1,10 +1,50 @@
-pub mod combinator;
-pub mod fs;
-
-#[derive(Debug, thiserror::Error)]
-pub enum Error {
-    #[error(transparent)]
-    Io(#[from] fs::IoError),
-    #[error("error: {0}")]
-    Parse(String),
-}
+//! Solutions for Advent of Code 2020.
+
+use adventofcode::runner::{self, Entry, Year};
+
+pub mod gen;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+
+/// Every solved day, as dispatched by `aoc 2020 DAY`; `aoc new` appends to it.
+pub const DAYS: &[(u32, Entry)] = &[
+    (1, runner::dispatch::<day01::Day01>),
+    (2, runner::dispatch::<day02::Day02>),
+    (3, runner::dispatch::<day03::Day03>),
+    (4, runner::dispatch::<day04::Day04>),
+    (5, runner::dispatch::<day05::Day05>),
+    (6, runner::dispatch::<day06::Day06>),
+    (7, runner::dispatch::<day07::Day07>),
+    (8, runner::dispatch::<day08::Day08>),
+    (9, runner::dispatch::<day09::Day09>),
+    (10, runner::dispatch::<day10::Day10>),
+    (11, runner::dispatch::<day11::Day11>),
+    (12, runner::dispatch::<day12::Day12>),
+    (13, runner::dispatch::<day13::Day13>),
+    (14, runner::dispatch::<day14::Day14>),
+    (15, runner::dispatch::<day15::Day15>),
+    (16, runner::dispatch::<day16::Day16>),
+    (17, runner::dispatch::<day17::Day17>),
+];
+
+pub const YEAR: Year = Year {
+    year: 2020,
+    root: env!("CARGO_MANIFEST_DIR"),
+    days: DAYS,
+};
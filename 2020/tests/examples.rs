@@ -0,0 +1,129 @@
+//! Runs every day against the samples from the puzzle statements.
+//!
+//! Each `tests/examples/dayN/NAME.txt` is fed to the `dayN` binary and its
+//! output is compared with the parts listed in `NAME.out`:
+//!
+//! ```text
+//! part1: 514579
+//! part2: 241861950
+//! ```
+//!
+//! A multi-line answer leaves the value after `partN:` empty and follows it
+//! with its lines, see [`answer::parse_labelled`]. Parts missing from the
+//! `.out` file are not checked. Any extra arguments are used as substring
+//! filters on `dayN/NAME`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+
+use adventofcode::{answer, Answer};
+
+const DAYS: &[(&str, &str)] = &[
+    ("day1", env!("CARGO_BIN_EXE_day1")),
+    ("day2", env!("CARGO_BIN_EXE_day2")),
+    ("day3", env!("CARGO_BIN_EXE_day3")),
+    ("day4", env!("CARGO_BIN_EXE_day4")),
+    ("day5", env!("CARGO_BIN_EXE_day5")),
+    ("day6", env!("CARGO_BIN_EXE_day6")),
+    ("day7", env!("CARGO_BIN_EXE_day7")),
+    ("day8", env!("CARGO_BIN_EXE_day8")),
+    ("day9", env!("CARGO_BIN_EXE_day9")),
+    ("day10", env!("CARGO_BIN_EXE_day10")),
+    ("day11", env!("CARGO_BIN_EXE_day11")),
+    ("day12", env!("CARGO_BIN_EXE_day12")),
+    ("day13", env!("CARGO_BIN_EXE_day13")),
+    ("day14", env!("CARGO_BIN_EXE_day14")),
+    ("day15", env!("CARGO_BIN_EXE_day15")),
+    ("day16", env!("CARGO_BIN_EXE_day16")),
+    ("day17", env!("CARGO_BIN_EXE_day17")),
+];
+
+fn sorted_entries(dir: &Path) -> Vec<PathBuf> {
+    let mut entries: Vec<_> = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("{}: {}", dir.display(), e))
+        .map(|e| e.unwrap().path())
+        .collect();
+    entries.sort();
+    entries
+}
+
+fn print_diff(expected: &str, actual: &str) {
+    for line in expected.lines() {
+        println!("    - {}", line);
+    }
+    for line in actual.lines() {
+        println!("    + {}", line);
+    }
+}
+
+fn main() {
+    let filters: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|a| !a.starts_with('-'))
+        .collect();
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/examples");
+
+    let mut passed = 0;
+    let mut failed = Vec::new();
+    for dir in sorted_entries(&root).into_iter().filter(|p| p.is_dir()) {
+        let day = dir.file_name().unwrap().to_string_lossy().into_owned();
+        for input in sorted_entries(&dir) {
+            if input.extension() != Some("txt".as_ref()) {
+                continue;
+            }
+            let name = format!("{}/{}", day, input.file_stem().unwrap().to_string_lossy());
+            if !filters.is_empty() && !filters.iter().any(|f| name.contains(f.as_str())) {
+                continue;
+            }
+
+            let expected = match fs::read_to_string(input.with_extension("out")) {
+                Ok(text) => answer::parse_labelled(&text, "part"),
+                Err(e) => {
+                    println!("{} ... FAILED\n    no expected answers: {}", name, e);
+                    failed.push(name);
+                    continue;
+                }
+            };
+            let bin = match DAYS.iter().find(|(d, _)| *d == day) {
+                Some((_, bin)) => bin,
+                None => {
+                    println!("{} ... FAILED\n    no binary for {}", name, day);
+                    failed.push(name);
+                    continue;
+                }
+            };
+
+            let output = Command::new(bin).arg(&input).output().unwrap();
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let actual = answer::parse_labelled(&stdout, "answer");
+            for (part, want) in &expected {
+                let test = format!("{} part{}", name, part);
+                match actual.get(part) {
+                    Some(got) if got.matches(&want.to_string()) => {
+                        println!("{} ... ok", test);
+                        passed += 1;
+                    }
+                    got => {
+                        println!("{} ... FAILED", test);
+                        let got = got.map_or_else(|| "<missing>".to_string(), Answer::to_string);
+                        print_diff(&want.to_string(), &got);
+                        if !output.status.success() {
+                            print!("{}", String::from_utf8_lossy(&output.stderr));
+                        }
+                        failed.push(test);
+                    }
+                }
+            }
+        }
+    }
+
+    println!();
+    println!("examples: {} passed; {} failed", passed, failed.len());
+    if !failed.is_empty() {
+        for test in &failed {
+            println!("    {}", test);
+        }
+        process::exit(1);
+    }
+}
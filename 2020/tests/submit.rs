@@ -0,0 +1,150 @@
+//! Runs `day1 submit` end to end against a stub server on localhost.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use std::{env, fs};
+
+const CORRECT: &str =
+    "<article><p>That's the right answer! You are <span>one gold star</span> closer.</p></article>";
+const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high. If you're stuck, try the subreddit.</p></article>";
+const TOO_RECENT: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 2s left to wait.</p></article>";
+
+/// A server that is never listening, for runs that must not post anything.
+const NOWHERE: &str = "http://127.0.0.1:1";
+
+/// Serves one canned page per request and returns the requests it saw.
+fn stub(pages: &'static [&'static str]) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let server = thread::spawn(move || {
+        let mut requests = Vec::new();
+        for page in pages {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+            requests.push(request);
+
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                page.len(),
+                page
+            )
+            .unwrap();
+        }
+        requests
+    });
+    (url, server)
+}
+
+fn workdir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-submit-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("input.txt"), "1721\n979\n366\n299\n675\n1456\n").unwrap();
+    dir
+}
+
+fn submit(dir: &Path, url: &str, part: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_day1"))
+        .args(["submit", part, "input.txt"])
+        .current_dir(dir)
+        .env("AOC_URL", url)
+        .env("AOC_SESSION", "s3cret")
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn store(dir: &Path) -> String {
+    fs::read_to_string(dir.join("answers/2020.toml")).unwrap()
+}
+
+#[test]
+fn correct_answer_is_recorded() {
+    let dir = workdir("correct");
+    let (url, server) = stub(&[CORRECT]);
+    let output = submit(&dir, &url, "1");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(stdout(&output), "day1 part1: 514579 ... correct\n");
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("POST /2020/day/1/answer HTTP/1.1\r\n"));
+    assert!(requests[0].contains("session=s3cret"));
+    assert!(requests[0].ends_with("\r\n\r\nlevel=1&answer=514579"));
+    assert!(store(&dir).contains("[day1]\npart1 = \"514579\"\n"));
+
+    let output = submit(&dir, NOWHERE, "1");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(stdout(&output), "day1 part1: 514579 ... already accepted\n");
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn wrong_answer_is_not_resubmitted() {
+    let dir = workdir("wrong");
+    let (url, server) = stub(&[TOO_HIGH]);
+    let output = submit(&dir, &url, "2");
+    assert!(!output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "day1 part2: 241861950 ... incorrect, too high\n"
+    );
+    assert_eq!(server.join().unwrap().len(), 1);
+    assert!(store(&dir)
+        .contains("[day1.rejected.part2]\nanswers = [\"241861950\"]\ntoo_high = \"241861950\"\n"));
+
+    let output = submit(&dir, NOWHERE, "2");
+    assert!(!output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "day1 part2: 241861950 ... not submitted, already rejected\n"
+    );
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn rate_limit_is_waited_out() {
+    let dir = workdir("wait");
+    let (url, server) = stub(&[TOO_RECENT, CORRECT]);
+    let output = submit(&dir, &url, "2");
+    assert!(!output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "day1 part2: 241861950 ... rate limited (wait 2s)\n"
+    );
+    assert!(store(&dir).starts_with("next_submit = "));
+
+    let start = Instant::now();
+    let output = submit(&dir, &url, "2");
+    assert!(output.status.success(), "{:?}", output);
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("waiting "));
+    assert!(start.elapsed() >= Duration::from_millis(900));
+    assert_eq!(stdout(&output), "day1 part2: 241861950 ... correct\n");
+    assert_eq!(server.join().unwrap().len(), 2);
+    let _ = fs::remove_dir_all(&dir);
+}
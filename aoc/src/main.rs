@@ -0,0 +1,105 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use adventofcode::runner::{self, Year};
+use adventofcode::{extract, fs, scaffold, watch, Error};
+
+/// Every event with a crate in the workspace.
+const YEARS: &[&Year] = &[&year2020::YEAR];
+
+const USAGE: &str = "usage: aoc YEAR DAY [ARGS...]
+       aoc new YEAR DAY
+       aoc watch YEAR DAY [--interval MS]
+       aoc extract YEAR DAY [PAGE] [--yes]
+Days run in their year's directory, so paths in ARGS are relative to it.";
+
+fn number(what: &str, arg: Option<String>) -> Result<u32, Error> {
+    let arg = arg.ok_or_else(|| Error::Parse(String::from(USAGE)))?;
+    arg.parse()
+        .map_err(|_| Error::Parse(format!("invalid {} `{}`\n{}", what, arg, USAGE)))
+}
+
+fn year_day(
+    first: Option<String>,
+    args: &mut impl Iterator<Item = String>,
+) -> Result<(&'static Year, u32), Error> {
+    let year = number("year", first)?;
+    let year = YEARS
+        .iter()
+        .find(|y| y.year == year)
+        .ok_or_else(|| Error::Parse(format!("there is no crate for {}", year)))?;
+    Ok((year, number("day", args.next())?))
+}
+
+fn unexpected(arg: &str) -> Error {
+    Error::Parse(format!("unexpected argument `{}`\n{}", arg, USAGE))
+}
+
+fn run(year: &Year, day: u32, args: Vec<String>) -> Result<bool, Error> {
+    let entry = year
+        .entry(day)
+        .ok_or_else(|| Error::Parse(format!("{} day {} is not solved yet", year.year, day)))?;
+    std::env::set_current_dir(year.root)
+        .map_err(|e| Error::Parse(format!("{}: {}", year.root, e)))?;
+    entry(args)
+}
+
+fn new(year: &Year, day: u32) -> Result<bool, Error> {
+    for path in scaffold::new_day(Path::new(year.root), year.year, day)? {
+        println!("wrote {}", path.display());
+    }
+    Ok(true)
+}
+
+fn watch(year: &Year, day: u32, mut args: impl Iterator<Item = String>) -> Result<bool, Error> {
+    let mut interval = Duration::from_millis(500);
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next().map(|ms| ms.parse())) {
+            ("--interval", Some(Ok(ms))) => interval = Duration::from_millis(ms),
+            _ => return Err(unexpected(&arg)),
+        }
+    }
+    watch::watch(Path::new(year.root), day, interval)
+}
+
+fn extract(year: &Year, day: u32, args: impl Iterator<Item = String>) -> Result<bool, Error> {
+    let root = Path::new(year.root);
+    let mut page = extract::default_page(root, day);
+    let mut assume_yes = false;
+    for arg in args {
+        match arg.as_str() {
+            "--yes" => assume_yes = true,
+            _ if arg.starts_with("--") => return Err(unexpected(&arg)),
+            _ => page = PathBuf::from(arg),
+        }
+    }
+    let examples = extract::propose(&extract::parse_page(&fs::read_input(&page)?));
+    if examples.is_empty() {
+        println!("no examples found in {}", page.display());
+        return Ok(false);
+    }
+    let dir = root.join(format!("tests/examples/day{}", day));
+    let stdin = std::io::stdin();
+    let written =
+        extract::confirm_and_write(&dir, &examples, assume_yes, stdin.lock(), std::io::stdout())?;
+    for path in &written {
+        println!("wrote {}", path.display());
+    }
+    Ok(true)
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let command = args.next();
+    let result = match command.as_deref() {
+        Some("new") => year_day(args.next(), &mut args).and_then(|(year, day)| new(year, day)),
+        Some("watch") => {
+            year_day(args.next(), &mut args).and_then(|(year, day)| watch(year, day, &mut args))
+        }
+        Some("extract") => {
+            year_day(args.next(), &mut args).and_then(|(year, day)| extract(year, day, &mut args))
+        }
+        _ => year_day(command, &mut args).and_then(|(year, day)| run(year, day, args.collect())),
+    };
+    runner::exit(result);
+}
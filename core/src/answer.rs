@@ -0,0 +1,302 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use super::ocr;
+
+/// A puzzle answer as submitted: a number, a word, or a block of text such
+/// as letters drawn with `#` and `.`.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Unsigned(u128),
+    Signed(i128),
+    Text(String),
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    pub fn grid<I, S>(lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Answer::Grid(lines.into_iter().map(Into::into).collect())
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        match self {
+            Answer::Grid(lines) => lines.len() > 1,
+            Answer::Text(text) => text.contains('\n'),
+            _ => false,
+        }
+    }
+
+    /// The letters drawn by a multi-line answer, if it is one.
+    pub fn read_letters(&self) -> Option<String> {
+        if self.is_multiline() {
+            ocr::recognize(&self.to_string()).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Numbers are compared by value whatever their variant.
+    fn as_i128(&self) -> Option<i128> {
+        match *self {
+            Answer::Unsigned(n) => i128::try_from(n).ok(),
+            Answer::Signed(n) => Some(n),
+            _ => None,
+        }
+    }
+}
+
+/// Compares rendered text, ignoring trailing whitespace on each line and
+/// surrounding blank lines, so stored answers survive editor round-trips.
+/// Drawn letters also match the word they spell.
+fn same_text(a: &Answer, b: &str) -> bool {
+    fn lines(s: &str) -> impl Iterator<Item = &str> {
+        s.trim_matches('\n').lines().map(str::trim_end)
+    }
+    let text = a.to_string();
+    if lines(&text).eq(lines(b)) {
+        return true;
+    }
+    if b.trim().contains('\n') {
+        a.read_letters().is_none() && ocr::recognize(b).is_ok_and(|word| text == word)
+    } else {
+        a.read_letters().is_some_and(|word| word == b.trim())
+    }
+}
+
+impl Answer {
+    /// Whether this answer is the one `expected` records: the same number or
+    /// text up to trailing whitespace, or drawn letters spelling the word.
+    /// Unlike `==`, which compares answers structurally, this is what
+    /// checking against stored answers uses.
+    pub fn matches(&self, expected: &str) -> bool {
+        same_text(self, expected)
+    }
+}
+
+/// Numbers compare by value whatever their variant; text and grids only
+/// equal the same variant with the same content.
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => a == b,
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            (Answer::Grid(a), Answer::Grid(b)) => a == b,
+            (a, b) => match (a.as_i128(), b.as_i128()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(n) => write!(f, "{}", n),
+            Answer::Signed(n) => write!(f, "{}", n),
+            Answer::Text(text) => f.write_str(text),
+            Answer::Grid(lines) => f.write_str(&lines.join("\n")),
+        }
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident as $wide:ty: $($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::$variant(n as $wide)
+            }
+        }
+    )*};
+}
+
+answer_from!(Unsigned as u128: u8, u16, u32, u64, u128, usize);
+answer_from!(Signed as i128: i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+fn labelled_header<'a>(line: &'a str, prefix: &str) -> Option<(&'a str, &'a str)> {
+    let (key, value) = line.split_once(':')?;
+    let part = key.trim().strip_prefix(prefix)?;
+    if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((part, value.trim()))
+}
+
+/// Reads `PREFIXn: value` lines, such as the runner's `answer1: 42`, keyed
+/// by `n`. A header with an empty value starts a multi-line answer made of
+/// the lines that follow it up to the next header.
+pub fn parse_labelled(text: &str, prefix: &str) -> BTreeMap<String, Answer> {
+    let mut answers = BTreeMap::<String, String>::new();
+    let mut block = None;
+    for line in text.lines() {
+        if let Some((part, value)) = labelled_header(line, prefix) {
+            answers.insert(part.to_string(), value.to_string());
+            block = value.is_empty().then(|| part.to_string());
+        } else if let Some(value) = block.as_ref().and_then(|part| answers.get_mut(part)) {
+            if !value.is_empty() {
+                value.push('\n');
+            }
+            value.push_str(line);
+        }
+    }
+    answers
+        .into_iter()
+        .map(|(part, value)| (part, Answer::from(value)))
+        .collect()
+}
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Unsigned(n) => match u64::try_from(*n) {
+                Ok(n) => serializer.serialize_u64(n),
+                Err(_) => serializer.serialize_str(&n.to_string()),
+            },
+            Answer::Signed(n) => match i64::try_from(*n) {
+                Ok(n) => serializer.serialize_i64(n),
+                Err(_) => serializer.serialize_str(&n.to_string()),
+            },
+            Answer::Text(text) => serializer.serialize_str(text),
+            Answer::Grid(lines) => lines.serialize(serializer),
+        }
+    }
+}
+
+struct AnswerVisitor;
+
+impl<'de> Visitor<'de> for AnswerVisitor {
+    type Value = Answer;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an integer, a string or a list of lines")
+    }
+
+    fn visit_u64<E: de::Error>(self, n: u64) -> Result<Answer, E> {
+        Ok(Answer::from(n))
+    }
+
+    fn visit_i64<E: de::Error>(self, n: i64) -> Result<Answer, E> {
+        Ok(Answer::from(n))
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<Answer, E> {
+        Ok(Answer::from(text))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Answer, A::Error> {
+        let mut lines = Vec::new();
+        while let Some(line) = seq.next_element::<String>()? {
+            lines.push(line);
+        }
+        Ok(Answer::Grid(lines))
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(Answer::from(42u8), Answer::from(42i64));
+        assert_ne!(Answer::from(42u8), Answer::from(-42i64));
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(Answer::from(-7i32).to_string(), "-7");
+        assert!(Answer::from(514579u32).matches("514579"));
+    }
+
+    #[test]
+    fn text() {
+        let word = Answer::from("ZKAUCFUC");
+        assert_eq!(word.to_string(), "ZKAUCFUC");
+        assert!(word.matches("ZKAUCFUC"));
+        assert!(!word.is_multiline());
+        assert_ne!(word, Answer::from(1u8));
+    }
+
+    #[test]
+    fn grid() {
+        let grid = Answer::grid(["#..#", "####"]);
+        assert!(grid.is_multiline());
+        assert_eq!(grid.to_string(), "#..#\n####");
+        assert!(grid.matches("#..#  \n####\n"));
+        assert_ne!(grid, Answer::from("#..#\n####"));
+    }
+
+    #[test]
+    fn letters() {
+        let drawn = Answer::grid([
+            "#..#.####",
+            "#..#.#...",
+            "####.###.",
+            "#..#.#...",
+            "#..#.#...",
+            "#..#.####",
+        ]);
+        assert_eq!(drawn.read_letters().as_deref(), Some("HE"));
+        assert!(drawn.matches("HE"));
+        assert!(Answer::from("HE").matches(&drawn.to_string()));
+        assert!(!drawn.matches("EH"));
+        // Equality stays structural, so it is transitive: the word is not
+        // equal to the drawing it matches.
+        assert_ne!(drawn, Answer::from("HE"));
+    }
+
+    #[test]
+    fn labelled() {
+        let output = "answer1: 42\nnoise\nanswer2:\n#..\n.#.\nanswer10: x";
+        let answers = parse_labelled(output, "answer");
+        assert_eq!(answers.len(), 3);
+        assert!(answers["1"].matches("42"));
+        assert!(answers["2"].matches("#..\n.#."));
+        assert_eq!(answers["10"], Answer::from("x"));
+        assert!(parse_labelled("answerx: 1\npart1: 2", "answer").is_empty());
+    }
+
+    #[test]
+    fn json() {
+        let answers = vec![
+            Answer::from(12u32),
+            Answer::from(-3i8),
+            Answer::from(u128::MAX),
+            Answer::from("abc"),
+            Answer::grid(["#.", ".#"]),
+        ];
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(
+            json,
+            r##"[12,-3,"340282366920938463463374607431768211455","abc",["#.",".#"]]"##
+        );
+        // Numbers too wide for JSON come back as text, which still matches.
+        let back: Vec<Answer> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.len(), answers.len());
+        for (back, answer) in back.iter().zip(&answers) {
+            assert!(back.matches(&answer.to_string()));
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::{fs, Error};
+
+/// Where the answers for `year` are kept, relative to the year's crate.
+pub fn answers_file(year: u32) -> PathBuf {
+    PathBuf::from(format!("answers/{}.toml", year))
+}
+
+/// Known-good answers for the real puzzle inputs, along with what `submit`
+/// learnt from rejected ones, stored as
+///
+/// ```toml
+/// next_submit = 1607058000
+///
+/// [day1]
+/// part1 = "514579"
+///
+/// [day1.rejected.part2]
+/// answers = ["300", "120"]
+/// too_high = "300"
+/// too_low = "120"
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    days: BTreeMap<u32, BTreeMap<u32, String>>,
+    rejected: BTreeMap<(u32, u32), Rejected>,
+    next_submit: Option<u64>,
+}
+
+/// Answers the server turned down for one part. `too_high` is the smallest
+/// answer known to be too high and `too_low` the largest known to be too low.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Rejected {
+    pub answers: Vec<String>,
+    pub too_high: Option<i128>,
+    pub too_low: Option<i128>,
+}
+
+impl Rejected {
+    /// Why `answer` is known to be wrong, if it is.
+    pub fn reason(&self, answer: &str) -> Option<String> {
+        if self.answers.iter().any(|a| a == answer) {
+            return Some(String::from("already rejected"));
+        }
+        let n = answer.parse::<i128>().ok()?;
+        match (self.too_high, self.too_low) {
+            (Some(high), _) if n >= high => Some(format!("{} was already too high", high)),
+            (_, Some(low)) if n <= low => Some(format!("{} was already too low", low)),
+            _ => None,
+        }
+    }
+}
+
+fn value_string(value: &toml::Value, name: &str) -> Result<String, Error> {
+    match value {
+        toml::Value::String(s) => Ok(s.clone()),
+        toml::Value::Integer(i) => Ok(i.to_string()),
+        _ => Err(Error::Parse(format!(
+            "{} must be a string or an integer",
+            name
+        ))),
+    }
+}
+
+fn value_number(value: &toml::Value, name: &str) -> Result<i128, Error> {
+    value_string(value, name)?
+        .parse()
+        .map_err(|_| Error::Parse(format!("{} must be a number", name)))
+}
+
+fn as_table<'a>(value: &'a toml::Value, name: &str) -> Result<&'a toml::Table, Error> {
+    value
+        .as_table()
+        .ok_or_else(|| Error::Parse(format!("`{}` is not a table", name)))
+}
+
+fn parse_rejected(value: &toml::Value, name: &str) -> Result<Rejected, Error> {
+    let mut rejected = Rejected::default();
+    for (key, value) in as_table(value, name)? {
+        let field = format!("{}.{}", name, key);
+        match key.as_str() {
+            "answers" => {
+                let answers = value
+                    .as_array()
+                    .ok_or_else(|| Error::Parse(format!("{} must be an array", field)))?;
+                rejected.answers = answers
+                    .iter()
+                    .map(|a| value_string(a, &field))
+                    .collect::<Result<_, _>>()?;
+            }
+            "too_high" => rejected.too_high = Some(value_number(value, &field)?),
+            "too_low" => rejected.too_low = Some(value_number(value, &field)?),
+            _ => return Err(Error::Parse(format!("invalid answers key `{}`", field))),
+        }
+    }
+    Ok(rejected)
+}
+
+fn key_number(key: &str, prefix: &str) -> Result<u32, Error> {
+    key.strip_prefix(prefix)
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| Error::Parse(format!("invalid answers key `{}`", key)))
+}
+
+impl Answers {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        match fs::read_optional(&path)? {
+            Some(text) => Self::parse(&text)
+                .map_err(|e| Error::Parse(format!("{}: {}", path.as_ref().display(), e))),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        fs::write_output(path, &self.to_string())
+    }
+
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let table: toml::Table = text.parse().map_err(|e| Error::Parse(format!("{}", e)))?;
+        let mut answers = Self::default();
+        for (day_key, parts) in &table {
+            if day_key == "next_submit" {
+                let time = parts.as_integer().and_then(|t| u64::try_from(t).ok());
+                answers.next_submit =
+                    Some(time.ok_or_else(|| Error::Parse(format!("invalid `{}`", day_key)))?);
+                continue;
+            }
+            let day = key_number(day_key, "day")?;
+            for (part_key, value) in as_table(parts, day_key)? {
+                if part_key == "rejected" {
+                    let name = format!("{}.{}", day_key, part_key);
+                    for (part_key, value) in as_table(value, &name)? {
+                        let part = key_number(part_key, "part")?;
+                        let name = format!("{}.{}", name, part_key);
+                        answers
+                            .rejected
+                            .insert((day, part), parse_rejected(value, &name)?);
+                    }
+                    continue;
+                }
+                let part = key_number(part_key, "part")?;
+                let value = value_string(value, &format!("{}.{}", day_key, part_key))?;
+                answers.set(day, part, value);
+            }
+        }
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.days.get(&day)?.get(&part).map(String::as_str)
+    }
+
+    pub fn set<V: Into<String>>(&mut self, day: u32, part: u32, value: V) {
+        self.days.entry(day).or_default().insert(part, value.into());
+    }
+
+    pub fn rejected(&self, day: u32, part: u32) -> Option<&Rejected> {
+        self.rejected.get(&(day, part))
+    }
+
+    /// Records a wrong answer; `hint` is `Greater` when the server said it
+    /// was too high and `Less` when too low.
+    pub fn reject(&mut self, day: u32, part: u32, answer: &str, hint: Option<Ordering>) {
+        let rejected = self.rejected.entry((day, part)).or_default();
+        if !rejected.answers.iter().any(|a| a == answer) {
+            rejected.answers.push(answer.to_string());
+        }
+        let n = answer.parse::<i128>().ok();
+        match (hint, n) {
+            (Some(Ordering::Greater), Some(n)) => {
+                rejected.too_high = Some(rejected.too_high.map_or(n, |h| h.min(n)))
+            }
+            (Some(Ordering::Less), Some(n)) => {
+                rejected.too_low = Some(rejected.too_low.map_or(n, |l| l.max(n)))
+            }
+            _ => {}
+        }
+    }
+
+    /// Unix time before which no answer should be submitted.
+    pub fn next_submit(&self) -> Option<u64> {
+        self.next_submit
+    }
+
+    pub fn set_next_submit(&mut self, time: u64) {
+        self.next_submit = Some(time);
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(time) = self.next_submit {
+            writeln!(f, "next_submit = {}", time)?;
+        }
+        let mut first = self.next_submit.is_none();
+        let mut section = |f: &mut fmt::Formatter<'_>, header: String| {
+            if !std::mem::take(&mut first) {
+                writeln!(f)?;
+            }
+            writeln!(f, "{}", header)
+        };
+        let mut days: Vec<u32> = self.days.keys().copied().collect();
+        days.extend(self.rejected.keys().map(|(day, _)| *day));
+        days.sort_unstable();
+        days.dedup();
+        for day in days {
+            if let Some(parts) = self.days.get(&day) {
+                section(f, format!("[day{}]", day))?;
+                for (part, value) in parts {
+                    writeln!(f, "part{} = {}", part, toml::Value::from(value.as_str()))?;
+                }
+            }
+            for ((_, part), rejected) in self.rejected.range((day, 0)..=(day, u32::MAX)) {
+                section(f, format!("[day{}.rejected.part{}]", day, part))?;
+                let answers: Vec<_> = rejected.answers.iter().map(String::as_str).collect();
+                writeln!(f, "answers = {}", toml::Value::from(answers))?;
+                if let Some(high) = rejected.too_high {
+                    writeln!(f, "too_high = \"{}\"", high)?;
+                }
+                if let Some(low) = rejected.too_low {
+                    writeln!(f, "too_low = \"{}\"", low)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_answers() {
+        let answers = Answers::parse("[day1]\npart1 = \"514579\"\npart2 = 241861950\n").unwrap();
+        assert_eq!(answers.get(1, 1), Some("514579"));
+        assert_eq!(answers.get(1, 2), Some("241861950"));
+        assert_eq!(answers.get(2, 1), None);
+        assert!(Answers::parse("[first]\npart1 = \"1\"").is_err());
+        assert!(Answers::parse("[day1]\npart1 = true").is_err());
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut answers = Answers::default();
+        answers.set(10, 1, "35");
+        answers.set(2, 2, "say \"hi\"");
+        answers.set(2, 1, "7");
+        let text = answers.to_string();
+        assert_eq!(
+            text,
+            "[day2]\npart1 = \"7\"\npart2 = 'say \"hi\"'\n\n[day10]\npart1 = \"35\"\n"
+        );
+        assert_eq!(Answers::parse(&text).unwrap(), answers);
+    }
+
+    #[test]
+    fn rejected_answers() {
+        let mut answers = Answers::default();
+        answers.set(1, 1, "514579");
+        answers.reject(1, 2, "300", Some(Ordering::Greater));
+        answers.reject(1, 2, "120", Some(Ordering::Less));
+        answers.reject(1, 2, "250", Some(Ordering::Greater));
+        answers.reject(1, 2, "abc", None);
+        answers.reject(3, 1, "7", None);
+        answers.set_next_submit(1607058000);
+
+        let rejected = answers.rejected(1, 2).unwrap();
+        assert_eq!(rejected.too_high, Some(250));
+        assert_eq!(rejected.too_low, Some(120));
+        assert_eq!(rejected.reason("abc").as_deref(), Some("already rejected"));
+        assert_eq!(
+            rejected.reason("260").as_deref(),
+            Some("250 was already too high")
+        );
+        assert_eq!(
+            rejected.reason("-4").as_deref(),
+            Some("120 was already too low")
+        );
+        assert_eq!(rejected.reason("200"), None);
+
+        let text = answers.to_string();
+        assert_eq!(
+            text,
+            "next_submit = 1607058000\n\n[day1]\npart1 = \"514579\"\n\n\
+             [day1.rejected.part2]\nanswers = [\"300\", \"120\", \"250\", \"abc\"]\n\
+             too_high = \"250\"\ntoo_low = \"120\"\n\n\
+             [day3.rejected.part1]\nanswers = [\"7\"]\n"
+        );
+        let parsed = Answers::parse(&text).unwrap();
+        assert_eq!(parsed, answers);
+        assert_eq!(parsed.get(3, 1), None);
+        assert!(Answers::parse("[day1.rejected.part1]\nanswers = \"7\"").is_err());
+    }
+}
@@ -0,0 +1,358 @@
+//! Cellular automata: a rule applied to every cell at once, either over a
+//! [`Grid`] or over the active points of an unbounded N-dimensional space.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::grid::Grid;
+
+/// The eight neighbouring directions as `(dx, dy)`.
+pub const DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Which cells of a grid count as neighbours of each cell. Neighbours are
+/// worked out once, so they may only depend on cells the rule never changes.
+pub trait Neighbourhood<T> {
+    fn neighbours(&self, grid: &Grid<T>, x: usize, y: usize) -> Vec<(usize, usize)>;
+}
+
+fn offset<T>(
+    grid: &Grid<T>,
+    (x, y): (usize, usize),
+    (dx, dy): (isize, isize),
+) -> Option<(usize, usize)> {
+    let (x, y) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+    grid.get(x, y).map(|_| (x, y))
+}
+
+/// The up to eight cells touching a cell.
+#[derive(Debug, Clone, Copy)]
+pub struct Adjacent;
+
+impl<T> Neighbourhood<T> for Adjacent {
+    fn neighbours(&self, grid: &Grid<T>, x: usize, y: usize) -> Vec<(usize, usize)> {
+        DIRECTIONS
+            .iter()
+            .filter_map(|&d| offset(grid, (x, y), d))
+            .collect()
+    }
+}
+
+/// The first cell in each of the eight directions that is not `transparent`.
+#[derive(Debug, Clone, Copy)]
+pub struct LineOfSight<F> {
+    pub transparent: F,
+}
+
+impl<T, F: Fn(&T) -> bool> Neighbourhood<T> for LineOfSight<F> {
+    fn neighbours(&self, grid: &Grid<T>, x: usize, y: usize) -> Vec<(usize, usize)> {
+        DIRECTIONS
+            .iter()
+            .filter_map(|&d| {
+                let mut at = offset(grid, (x, y), d)?;
+                while (self.transparent)(grid.get(at.0, at.1)?) {
+                    at = offset(grid, at, d)?;
+                }
+                Some(at)
+            })
+            .collect()
+    }
+}
+
+/// The neighbours of one cell, as handed to a rule.
+pub struct Neighbours<'a, T> {
+    cells: &'a [T],
+    indices: std::slice::Iter<'a, usize>,
+}
+
+impl<'a, T> Iterator for Neighbours<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.indices.next().map(|&i| &self.cells[i])
+    }
+}
+
+/// How a run ended, see [`Automaton::run`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// Nothing changes any more after `generation`.
+    Stable { generation: usize },
+    /// The state at `generation` repeats every `period` generations.
+    Cycle { start: usize, period: usize },
+    /// Still changing after the generation limit.
+    Unsettled,
+}
+
+/// A grid updated generation by generation, writing each generation into a
+/// second buffer that is then swapped in.
+#[derive(Debug, Clone)]
+pub struct Automaton<T> {
+    current: Grid<T>,
+    next: Grid<T>,
+    neighbours: Vec<Vec<usize>>,
+    generation: usize,
+}
+
+impl<T: Clone + PartialEq> Automaton<T> {
+    pub fn new<N: Neighbourhood<T>>(grid: Grid<T>, neighbourhood: &N) -> Self {
+        let width = grid.width();
+        let neighbours = grid
+            .positions()
+            .map(|(x, y)| {
+                let cells = neighbourhood.neighbours(&grid, x, y);
+                cells.into_iter().map(|(x, y)| y * width + x).collect()
+            })
+            .collect();
+        Automaton {
+            next: grid.clone(),
+            current: grid,
+            neighbours,
+            generation: 0,
+        }
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.current
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Applies `rule` to every cell, returning whether any of them changed.
+    pub fn step<R>(&mut self, rule: R) -> bool
+    where
+        R: Fn(&T, Neighbours<T>) -> T,
+    {
+        let cells = self.current.cells();
+        let mut changed = false;
+        for (i, out) in self.next.cells_mut().iter_mut().enumerate() {
+            let neighbours = Neighbours {
+                cells,
+                indices: self.neighbours[i].iter(),
+            };
+            *out = rule(&cells[i], neighbours);
+            changed |= *out != cells[i];
+        }
+        std::mem::swap(&mut self.current, &mut self.next);
+        self.generation += 1;
+        changed
+    }
+
+    /// Steps until nothing changes, giving up after `limit` generations.
+    pub fn run_to_fixed_point<R>(&mut self, rule: R, limit: usize) -> Option<usize>
+    where
+        R: Fn(&T, Neighbours<T>) -> T,
+    {
+        for _ in 0..limit {
+            if !self.step(&rule) {
+                return Some(self.generation - 1);
+            }
+        }
+        None
+    }
+}
+
+impl<T: Clone + Eq + Hash> Automaton<T> {
+    /// Steps until the grid settles or repeats an earlier generation, for at
+    /// most `limit` generations. Every generation seen is kept to spot cycles.
+    pub fn run<R>(&mut self, rule: R, limit: usize) -> Outcome
+    where
+        R: Fn(&T, Neighbours<T>) -> T,
+    {
+        let mut seen = HashMap::new();
+        seen.insert(self.current.cells().to_vec(), self.generation);
+        for _ in 0..limit {
+            if !self.step(&rule) {
+                return Outcome::Stable {
+                    generation: self.generation - 1,
+                };
+            }
+            if let Some(&start) = seen.get(self.current.cells()) {
+                return Outcome::Cycle {
+                    start,
+                    period: self.generation - start,
+                };
+            }
+            seen.insert(self.current.cells().to_vec(), self.generation);
+        }
+        Outcome::Unsettled
+    }
+}
+
+/// Offsets to the `3^D - 1` points around a point in `D` dimensions.
+pub fn moore<const D: usize>() -> Vec<[i64; D]> {
+    let mut offsets = vec![[0; D]];
+    for axis in 0..D {
+        offsets = offsets
+            .into_iter()
+            .flat_map(|o| {
+                (-1..=1).map(move |d| {
+                    let mut o = o;
+                    o[axis] = d;
+                    o
+                })
+            })
+            .collect();
+    }
+    offsets.retain(|o| o.iter().any(|&d| d != 0));
+    offsets
+}
+
+/// The active points of an unbounded `D`-dimensional space where every point
+/// sees its [`moore`] neighbours. Only active points are stored.
+#[derive(Debug, Clone)]
+pub struct SparseAutomaton<const D: usize> {
+    active: HashSet<[i64; D]>,
+    offsets: Vec<[i64; D]>,
+    /// Reused between steps to count active neighbours.
+    counts: HashMap<[i64; D], usize>,
+}
+
+impl<const D: usize> SparseAutomaton<D> {
+    pub fn new<I: IntoIterator<Item = [i64; D]>>(active: I) -> Self {
+        SparseAutomaton {
+            active: active.into_iter().collect(),
+            offsets: moore(),
+            counts: HashMap::new(),
+        }
+    }
+
+    /// The `true` cells of `grid` on the plane where every other coordinate
+    /// is 0. Needs at least the two dimensions of the grid:
+    ///
+    /// ```compile_fail
+    /// # use adventofcode::{automaton::SparseAutomaton, grid::Grid};
+    /// let grid = Grid::parse("#", |c| Some(c == '#')).unwrap();
+    /// SparseAutomaton::<1>::from_grid(&grid);
+    /// ```
+    pub fn from_grid(grid: &Grid<bool>) -> Self {
+        const { assert!(D >= 2, "a grid needs at least two dimensions") };
+        let active = grid
+            .positions()
+            .filter(|&(x, y)| grid.get(x, y) == Some(&true))
+            .map(|(x, y)| {
+                let mut point = [0; D];
+                point[0] = x as i64;
+                point[1] = y as i64;
+                point
+            });
+        Self::new(active)
+    }
+
+    pub fn len(&self) -> usize {
+        self.active.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    pub fn is_active(&self, point: &[i64; D]) -> bool {
+        self.active.contains(point)
+    }
+
+    /// Decides every point's next state from whether it is active and how
+    /// many active neighbours it has; returns whether anything changed.
+    pub fn step<R: Fn(bool, usize) -> bool>(&mut self, rule: R) -> bool {
+        self.counts.clear();
+        for point in &self.active {
+            self.counts.entry(*point).or_insert(0);
+            for offset in &self.offsets {
+                let mut near = *point;
+                for (c, d) in near.iter_mut().zip(offset) {
+                    *c += d;
+                }
+                *self.counts.entry(near).or_insert(0) += 1;
+            }
+        }
+        let next: HashSet<_> = self
+            .counts
+            .iter()
+            .filter(|&(point, &count)| rule(self.active.contains(point), count))
+            .map(|(point, _)| *point)
+            .collect();
+        let changed = next != self.active;
+        self.active = next;
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(alive: &bool, neighbours: Neighbours<bool>) -> bool {
+        matches!(
+            (alive, neighbours.filter(|&&n| n).count()),
+            (true, 2) | (_, 3)
+        )
+    }
+
+    fn cells(c: char) -> Option<bool> {
+        Some(c == '#')
+    }
+
+    #[test]
+    fn neighbourhoods() {
+        let grid = Grid::parse("#..\n...\n..#\n", cells).unwrap();
+        assert_eq!(Adjacent.neighbours(&grid, 0, 0), [(1, 0), (0, 1), (1, 1)]);
+        assert_eq!(Adjacent.neighbours(&grid, 1, 1).len(), 8);
+        let sight = LineOfSight {
+            transparent: |c: &bool| !c,
+        };
+        assert_eq!(sight.neighbours(&grid, 0, 0), [(2, 2)]);
+        assert_eq!(sight.neighbours(&grid, 1, 1), [(0, 0), (2, 2)]);
+    }
+
+    #[test]
+    fn fixed_point_and_cycles() {
+        let block = Grid::parse(".....\n.##..\n.##..\n.....\n", cells).unwrap();
+        let mut automaton = Automaton::new(block.clone(), &Adjacent);
+        assert_eq!(automaton.run_to_fixed_point(life, 10), Some(0));
+        assert_eq!(automaton.grid(), &block);
+
+        let blinker = Grid::parse(".....\n..#..\n..#..\n..#..\n.....\n", cells).unwrap();
+        let mut automaton = Automaton::new(blinker.clone(), &Adjacent);
+        assert!(automaton.step(life));
+        assert_ne!(automaton.grid(), &blinker);
+        assert_eq!(
+            automaton.run(life, 10),
+            Outcome::Cycle {
+                start: 1,
+                period: 2
+            }
+        );
+        assert_eq!(automaton.run_to_fixed_point(life, 10), None);
+        assert_eq!(
+            Automaton::new(block, &Adjacent).run(life, 10),
+            Outcome::Stable { generation: 0 }
+        );
+    }
+
+    #[test]
+    fn sparse() {
+        assert_eq!(moore::<2>().len(), 8);
+        assert_eq!(moore::<4>().len(), 80);
+        let grid = Grid::parse(".#.\n..#\n###\n", cells).unwrap();
+        let mut space = SparseAutomaton::<3>::from_grid(&grid);
+        assert!(space.is_active(&[1, 0, 0]));
+        let rule = |active, n| matches!((active, n), (true, 2) | (_, 3));
+        assert!(space.step(rule));
+        assert_eq!(space.len(), 11);
+        for _ in 1..6 {
+            space.step(rule);
+        }
+        assert_eq!(space.len(), 112);
+        assert!(!SparseAutomaton::<2>::new([]).step(rule));
+    }
+}
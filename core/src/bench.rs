@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use super::{fs, Error};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [u64]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        let mid = samples.len() / 2;
+        let median_ns = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+        let total: u128 = samples.iter().map(|&s| s as u128).sum();
+        Some(Stats {
+            min_ns: samples[0],
+            median_ns,
+            mean_ns: (total / samples.len() as u128) as u64,
+        })
+    }
+}
+
+/// Runs `f` `warmup` times untimed, then `samples` more times.
+///
+/// Every result is passed through [`black_box`] and the last one is returned,
+/// so callers can feed a timed parse into the parts that follow it.
+pub fn measure<T, F: FnMut() -> T>(warmup: usize, samples: usize, mut f: F) -> (T, Stats) {
+    for _ in 0..warmup {
+        black_box(f());
+    }
+    let mut times = Vec::with_capacity(samples.max(1));
+    let mut last = None;
+    for _ in 0..samples.max(1) {
+        let start = Instant::now();
+        let value = black_box(f());
+        times.push(start.elapsed().as_nanos() as u64);
+        last = Some(value);
+    }
+    let stats = Stats::from_samples(&mut times).expect("at least one sample");
+    (last.expect("at least one sample"), stats)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    pub warmup: usize,
+    pub samples: usize,
+    pub timings: BTreeMap<String, Stats>,
+}
+
+/// Benchmark reports keyed by `dayN`, as stored in the JSON results file.
+pub type Reports = BTreeMap<String, Report>;
+
+pub fn load_reports<P: AsRef<Path>>(path: P) -> Result<Reports, Error> {
+    match fs::read_optional(&path)? {
+        Some(text) => serde_json::from_str(&text)
+            .map_err(|e| Error::Parse(format!("{}: {}", path.as_ref().display(), e))),
+        None => Ok(Reports::new()),
+    }
+}
+
+pub fn save_reports<P: AsRef<Path>>(path: P, reports: &Reports) -> Result<(), Error> {
+    let mut text =
+        serde_json::to_string_pretty(reports).map_err(|e| Error::Parse(e.to_string()))?;
+    text.push('\n');
+    fs::write_output(path, &text)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub name: String,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+}
+
+impl Regression {
+    pub fn percent(&self) -> f64 {
+        (self.current_ns as f64 / self.baseline_ns.max(1) as f64 - 1.0) * 100.0
+    }
+}
+
+/// Timings whose median got slower than the baseline by more than `threshold` percent.
+pub fn regressions(current: &Report, baseline: &Report, threshold: f64) -> Vec<Regression> {
+    current
+        .timings
+        .iter()
+        .filter_map(|(name, stats)| {
+            let base = baseline.timings.get(name)?;
+            let limit = base.median_ns as f64 * (1.0 + threshold / 100.0);
+            (stats.median_ns as f64 > limit).then(|| Regression {
+                name: name.clone(),
+                baseline_ns: base.median_ns,
+                current_ns: stats.median_ns,
+            })
+        })
+        .collect()
+}
+
+pub fn format_ns(ns: u64) -> String {
+    match ns {
+        0..=999 => format!("{}ns", ns),
+        1_000..=999_999 => format!("{:.2}µs", ns as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.2}ms", ns as f64 / 1e6),
+        _ => format!("{:.2}s", ns as f64 / 1e9),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(parse: u64, part1: u64) -> Report {
+        let stats = |median_ns| Stats {
+            min_ns: median_ns,
+            median_ns,
+            mean_ns: median_ns,
+        };
+        Report {
+            warmup: 0,
+            samples: 1,
+            timings: [("parse", stats(parse)), ("part1", stats(part1))]
+                .into_iter()
+                .map(|(name, s)| (name.to_string(), s))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn stats() {
+        assert_eq!(Stats::from_samples(&mut []), None);
+        let stats = Stats::from_samples(&mut [9, 1, 5, 3]).unwrap();
+        assert_eq!(
+            stats,
+            Stats {
+                min_ns: 1,
+                median_ns: 4,
+                mean_ns: 4
+            }
+        );
+        assert_eq!(Stats::from_samples(&mut [7, 2, 3]).unwrap().median_ns, 3);
+    }
+
+    #[test]
+    fn regression_threshold() {
+        let baseline = report(1000, 1000);
+        assert!(regressions(&report(1100, 900), &baseline, 10.0).is_empty());
+        let found = regressions(&report(1101, 2000), &baseline, 10.0);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[1].name, "part1");
+        assert_eq!(found[1].percent(), 100.0);
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(format_ns(999), "999ns");
+        assert_eq!(format_ns(12_345), "12.35µs");
+        assert_eq!(format_ns(4_560_000), "4.56ms");
+        assert_eq!(format_ns(1_230_000_000), "1.23s");
+    }
+}
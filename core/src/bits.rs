@@ -0,0 +1,188 @@
+//! Bit strings, `0`/`1`/`X` masks over them and a sparse memory to write
+//! masked values to.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::Error;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum BitsError {
+    #[error("unexpected `{found}` at bit {at}")]
+    Invalid { found: char, at: usize },
+    #[error("{0} bits do not fit in 64")]
+    TooLong(usize),
+}
+
+/// Reads `s` as a number, most significant bit first, with `bit` telling
+/// whether each character is a one or a zero, or not a bit at all.
+pub fn parse_bits<F>(s: &str, bit: F) -> Result<u64, BitsError>
+where
+    F: Fn(char) -> Option<bool>,
+{
+    let len = s.chars().count();
+    if len > 64 {
+        return Err(BitsError::TooLong(len));
+    }
+    s.chars().enumerate().try_fold(0, |n, (at, found)| {
+        let one = bit(found).ok_or(BitsError::Invalid { found, at })?;
+        Ok(n << 1 | one as u64)
+    })
+}
+
+/// A mask such as `XXXX1XX0X`, its last character covering bit 0. Bits left
+/// of the mask pass through [`apply`](Mask::apply) and
+/// [`addresses`](Mask::addresses) unchanged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Mask {
+    pub ones: u64,
+    pub zeros: u64,
+    pub floating: u64,
+}
+
+impl FromStr for Mask {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let class = |of: char| {
+            move |c| match c {
+                '0' | '1' | 'X' => Some(c == of),
+                _ => None,
+            }
+        };
+        Ok(Mask {
+            ones: parse_bits(s, class('1'))?,
+            zeros: parse_bits(s, class('0'))?,
+            floating: parse_bits(s, class('X'))?,
+        })
+    }
+}
+
+impl Mask {
+    /// `value` with the mask's ones and zeros written over it.
+    pub fn apply(&self, value: u64) -> u64 {
+        value & !self.zeros | self.ones
+    }
+
+    /// Every address `address` decodes to: the mask's ones are set and its
+    /// floating bits take all their combinations.
+    pub fn addresses(&self, address: u64) -> Floating {
+        Floating {
+            base: (address | self.ones) & !self.floating,
+            floating: self.floating,
+            next: Some(0),
+        }
+    }
+
+    /// Number of `X` bits; [`addresses`](Mask::addresses) yields two to the
+    /// power of it.
+    pub fn floating_count(&self) -> u32 {
+        self.floating.count_ones()
+    }
+}
+
+/// The expansions of a floating address, see [`Mask::addresses`].
+#[derive(Debug, Clone)]
+pub struct Floating {
+    base: u64,
+    floating: u64,
+    /// The next combination of floating bits, counting up through subsets.
+    next: Option<u64>,
+}
+
+impl Iterator for Floating {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let bits = self.next?;
+        let following = bits.wrapping_sub(self.floating) & self.floating;
+        self.next = (following != 0).then_some(following);
+        Some(self.base | bits)
+    }
+}
+
+/// Memory where only written addresses take up space; the rest read as 0.
+#[derive(Debug, Clone, Default)]
+pub struct Memory {
+    cells: HashMap<u64, u64>,
+}
+
+impl Memory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn read(&self, address: u64) -> u64 {
+        self.cells.get(&address).copied().unwrap_or(0)
+    }
+
+    pub fn write(&mut self, address: u64, value: u64) {
+        self.cells.insert(address, value);
+    }
+
+    /// Number of addresses written to.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn sum(&self) -> u128 {
+        self.cells.values().map(|&v| v as u128).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bit_strings() {
+        let bit = |c| match c {
+            'B' => Some(true),
+            'F' => Some(false),
+            _ => None,
+        };
+        assert_eq!(parse_bits("BFFFBBF", bit), Ok(70));
+        assert_eq!(parse_bits("", bit), Ok(0));
+        assert_eq!(
+            parse_bits("BFR", bit),
+            Err(BitsError::Invalid { found: 'R', at: 2 })
+        );
+        assert_eq!(
+            parse_bits(&"B".repeat(65), bit),
+            Err(BitsError::TooLong(65))
+        );
+        assert_eq!(parse_bits(&"B".repeat(64), bit), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn masks() {
+        let mask: Mask = "XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X".parse().unwrap();
+        assert_eq!(mask.apply(11), 73);
+        assert_eq!(mask.apply(101), 101);
+        assert_eq!(mask.apply(0), 64);
+        assert!("X1Y".parse::<Mask>().is_err());
+
+        let mask: Mask = "000000000000000000000000000000X1001X".parse().unwrap();
+        let mut addresses: Vec<_> = mask.addresses(42).collect();
+        addresses.sort_unstable();
+        assert_eq!(addresses, [26, 27, 58, 59]);
+        assert_eq!(mask.floating_count(), 2);
+        assert_eq!(Mask::default().addresses(7).collect::<Vec<_>>(), [7]);
+    }
+
+    #[test]
+    fn sparse_memory() {
+        let mut memory = Memory::new();
+        assert!(memory.is_empty());
+        memory.write(1 << 35, 5);
+        memory.write(8, 7);
+        memory.write(8, u64::MAX);
+        assert_eq!((memory.read(8), memory.read(9)), (u64::MAX, 0));
+        assert_eq!(memory.len(), 2);
+        assert_eq!(memory.sum(), u64::MAX as u128 + 5);
+    }
+}
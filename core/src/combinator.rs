@@ -8,7 +8,7 @@ impl Increment for usize {
     type Max = usize;
 
     fn over(&self, max: &Self::Max) -> bool {
-        (self + 1) >= *max
+        *self >= *max
     }
 
     fn inc(&mut self, max: &Self::Max) {
@@ -28,12 +28,13 @@ where
     type Max = M;
 
     fn over(&self, max: &Self::Max) -> bool {
-        self.0.over(max)
+        self.0.over(max) && self.1.over(max)
     }
 
     fn inc(&mut self, max: &Self::Max) {
+        let carry = self.1.over(max);
         self.1.inc(max);
-        if self.1.over(max) {
+        if carry {
             self.0.inc(max);
         }
     }
@@ -45,35 +46,40 @@ where
 {
     data: Vec<T>,
     indexs: I,
+    done: bool,
 }
 
-impl<'a, T: Copy> Iterator for Combinator<T, (usize, usize)> {
+impl<T: Copy> Iterator for Combinator<T, (usize, usize)> {
     type Item = (T, T);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let (ia, ib) = self.indexs;
-        if let (Some(a), Some(b)) = (self.data.get(ia), self.data.get(ib)) {
-            self.indexs.inc(&self.data.len());
-            Some((*a, *b))
-        } else {
-            None
+        let max = self.data.len().checked_sub(1)?;
+        while !self.done {
+            let (ia, ib) = self.indexs;
+            self.done = self.indexs.over(&max);
+            self.indexs.inc(&max);
+            if ia < ib {
+                return Some((self.data[ia], self.data[ib]));
+            }
         }
+        None
     }
 }
 
-impl<'a, T: Copy> Iterator for Combinator<T, ((usize, usize), usize)> {
+impl<T: Copy> Iterator for Combinator<T, ((usize, usize), usize)> {
     type Item = (T, T, T);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let ((ia, ib), ic) = self.indexs;
-        if let (Some(a), Some(b), Some(c)) =
-            (self.data.get(ia), self.data.get(ib), self.data.get(ic))
-        {
-            self.indexs.inc(&self.data.len());
-            Some((*a, *b, *c))
-        } else {
-            None
+        let max = self.data.len().checked_sub(1)?;
+        while !self.done {
+            let ((ia, ib), ic) = self.indexs;
+            self.done = self.indexs.over(&max);
+            self.indexs.inc(&max);
+            if ia < ib && ib < ic {
+                return Some((self.data[ia], self.data[ib], self.data[ic]));
+            }
         }
+        None
     }
 }
 
@@ -81,6 +87,7 @@ pub fn pairs<T>(data: Vec<T>) -> Combinator<T, (usize, usize)> {
     Combinator {
         data,
         indexs: (0, 0),
+        done: false,
     }
 }
 
@@ -88,6 +95,7 @@ pub fn trits<T>(data: Vec<T>) -> Combinator<T, ((usize, usize), usize)> {
     Combinator {
         data,
         indexs: ((0, 0), 0),
+        done: false,
     }
 }
 
@@ -126,4 +134,18 @@ mod tests {
         val.inc(&33);
         assert_eq!(val, ((32, 33), 0));
     }
+
+    #[test]
+    fn pairs_distinct() {
+        let all: Vec<_> = pairs(vec![1, 2, 3]).collect();
+        assert_eq!(all, vec![(1, 2), (1, 3), (2, 3)]);
+        assert_eq!(pairs(Vec::<u32>::new()).count(), 0);
+    }
+
+    #[test]
+    fn trits_distinct() {
+        let all: Vec<_> = trits(vec![1, 2, 3, 4]).collect();
+        assert_eq!(all, vec![(1, 2, 3), (1, 2, 4), (1, 3, 4), (2, 3, 4)]);
+        assert_eq!(trits(vec![1, 2]).count(), 0);
+    }
 }
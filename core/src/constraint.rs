@@ -0,0 +1,86 @@
+//! Matching items to distinct values by elimination, as when deducing which
+//! column holds which field.
+
+use std::collections::BTreeSet;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ConstraintError {
+    #[error("item {0} has no value left")]
+    Unsatisfiable(usize),
+    #[error("items {0:?} could each take several values")]
+    Ambiguous(Vec<usize>),
+}
+
+/// Gives each item one of its `candidates`, no two items the same value.
+/// Items down to a single candidate take it and rule it out for the others,
+/// until nothing changes. Anything still undecided is reported as ambiguous
+/// rather than guessed.
+pub fn eliminate(mut candidates: Vec<BTreeSet<usize>>) -> Result<Vec<usize>, ConstraintError> {
+    let mut assigned: Vec<Option<usize>> = vec![None; candidates.len()];
+    loop {
+        let mut progress = false;
+        for item in 0..candidates.len() {
+            if assigned[item].is_some() {
+                continue;
+            }
+            let mut left = candidates[item].iter();
+            let value = match (left.next(), left.next()) {
+                (None, _) => return Err(ConstraintError::Unsatisfiable(item)),
+                (Some(&value), None) => value,
+                _ => continue,
+            };
+            assigned[item] = Some(value);
+            for (other, c) in candidates.iter_mut().enumerate() {
+                if other != item {
+                    c.remove(&value);
+                }
+            }
+            progress = true;
+        }
+        if !progress {
+            break;
+        }
+    }
+    let undecided: Vec<usize> = (0..assigned.len())
+        .filter(|&item| assigned[item].is_none())
+        .collect();
+    if !undecided.is_empty() {
+        return Err(ConstraintError::Ambiguous(undecided));
+    }
+    Ok(assigned.into_iter().flatten().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sets(candidates: &[&[usize]]) -> Vec<BTreeSet<usize>> {
+        candidates
+            .iter()
+            .map(|c| c.iter().copied().collect())
+            .collect()
+    }
+
+    #[test]
+    fn staircase() {
+        let candidates = sets(&[&[0, 1, 2], &[1], &[1, 2]]);
+        assert_eq!(eliminate(candidates), Ok(vec![0, 1, 2]));
+        let candidates = sets(&[&[0, 1, 3], &[1, 3], &[0, 1, 2, 3], &[3]]);
+        assert_eq!(eliminate(candidates), Ok(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn failures() {
+        let candidates = sets(&[&[0, 1], &[0, 1], &[2]]);
+        assert_eq!(
+            eliminate(candidates),
+            Err(ConstraintError::Ambiguous(vec![0, 1]))
+        );
+        let candidates = sets(&[&[0], &[0]]);
+        assert_eq!(
+            eliminate(candidates),
+            Err(ConstraintError::Unsatisfiable(1))
+        );
+        assert_eq!(eliminate(Vec::new()), Ok(Vec::new()));
+    }
+}
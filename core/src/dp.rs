@@ -0,0 +1,172 @@
+//! Dynamic programming helpers: memoised recursion and counting over sorted
+//! sequences.
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::ops::Sub;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum DpError {
+    #[error("count overflows {kind} at index {at}")]
+    Overflow { kind: &'static str, at: usize },
+}
+
+/// A recursive function whose results are cached by key. The function gets
+/// a callback for its recursive calls, which go through the cache too:
+///
+/// ```
+/// use adventofcode::dp::Memo;
+///
+/// let mut fib = Memo::new(|fib: &mut dyn FnMut(u64) -> u64, n: u64| {
+///     if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+/// });
+/// assert_eq!(fib.get(90), 2880067194370816120);
+/// ```
+pub struct Memo<K, V, F> {
+    cache: HashMap<K, V>,
+    f: F,
+}
+
+impl<K, V, F> Memo<K, V, F>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    pub fn new(f: F) -> Self {
+        Memo {
+            cache: HashMap::new(),
+            f,
+        }
+    }
+
+    pub fn get(&mut self, key: K) -> V {
+        call(&mut self.cache, &self.f, key)
+    }
+
+    /// Number of cached results.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+fn call<K, V, F>(cache: &mut HashMap<K, V>, f: &F, key: K) -> V
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    if let Some(value) = cache.get(&key) {
+        return value.clone();
+    }
+    let value = f(&mut |k| call(cache, f, k), key.clone());
+    cache.insert(key, value.clone());
+    value
+}
+
+/// Unsigned integers that counts can be kept in without wrapping.
+pub trait Count: Copy {
+    const NAME: &'static str;
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! count {
+    ($($t:ty),*) => {$(
+        impl Count for $t {
+            const NAME: &'static str = stringify!($t);
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+        }
+    )*};
+}
+
+count!(u32, u64, u128, usize);
+
+/// Number of ways to walk `sorted` from its first value to its last, only
+/// stepping forward by at most `max_step` at a time.
+pub fn count_paths<C: Count>(sorted: &[u64], max_step: u64) -> Result<C, DpError> {
+    let mut ways: Vec<C> = Vec::with_capacity(sorted.len());
+    for (i, &value) in sorted.iter().enumerate() {
+        let reachable = sorted[..i]
+            .iter()
+            .enumerate()
+            .rev()
+            .take_while(|&(_, &before)| value - before <= max_step);
+        let mut total = if i == 0 { C::ONE } else { C::ZERO };
+        for (j, _) in reachable {
+            total = total.checked_add(ways[j]).ok_or(DpError::Overflow {
+                kind: C::NAME,
+                at: i,
+            })?;
+        }
+        ways.push(total);
+    }
+    Ok(ways.last().copied().unwrap_or(C::ZERO))
+}
+
+/// How often each difference between neighbours of `sorted` occurs.
+pub fn differences<T>(sorted: &[T]) -> BTreeMap<T, usize>
+where
+    T: Copy + Ord + Sub<Output = T>,
+{
+    let mut histogram = BTreeMap::new();
+    for pair in sorted.windows(2) {
+        *histogram.entry(pair[1] - pair[0]).or_insert(0) += 1;
+    }
+    histogram
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memoised_recursion() {
+        let mut paths = Memo::new(|paths: &mut dyn FnMut((u32, u32)) -> u64, (x, y)| {
+            if x == 0 || y == 0 {
+                1
+            } else {
+                paths((x - 1, y)) + paths((x, y - 1))
+            }
+        });
+        assert_eq!(paths.get((2, 2)), 6);
+        assert_eq!(paths.get((16, 16)), 601080390);
+        assert_eq!(paths.len(), 17 * 17 - 1);
+    }
+
+    #[test]
+    fn counting() {
+        assert_eq!(count_paths::<u64>(&[0, 1, 2, 3], 3), Ok(4));
+        assert_eq!(count_paths::<u64>(&[0, 1, 5], 3), Ok(0));
+        assert_eq!(count_paths::<u64>(&[7], 3), Ok(1));
+        assert_eq!(count_paths::<u64>(&[], 3), Ok(0));
+
+        let steps: Vec<u64> = (0..100).collect();
+        assert_eq!(
+            count_paths::<u32>(&steps, 1 << 20),
+            Err(DpError::Overflow {
+                kind: "u32",
+                at: 33
+            })
+        );
+        assert_eq!(count_paths::<u128>(&steps, 1 << 20), Ok(1 << 98));
+    }
+
+    #[test]
+    fn histogram() {
+        let diffs = differences(&[0, 1, 4, 5, 6, 9]);
+        assert_eq!(diffs, BTreeMap::from([(1, 3), (3, 2)]));
+        assert!(differences::<u8>(&[3]).is_empty());
+    }
+}
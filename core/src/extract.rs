@@ -0,0 +1,297 @@
+//! Proposes example tests from a saved puzzle page, see `aoc extract`.
+//!
+//! Each part of a puzzle is an `<article>`. Its first `<pre><code>` block is
+//! taken as the example input and its last emphasised inline code
+//! (`<code><em>514579</em></code>`) as the expected answer. Part 2 usually
+//! reuses the part 1 example, in which case both answers go to one file.
+
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use crate::{fs, Error};
+
+/// The code blocks and emphasised answers of one part's description.
+#[derive(Debug, Default, PartialEq)]
+pub struct Part {
+    pub blocks: Vec<String>,
+    pub answers: Vec<String>,
+}
+
+/// An example input with the answers expected for it, keyed by part.
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub answers: BTreeMap<u32, String>,
+}
+
+impl Example {
+    /// The `.out` file read by the example harness.
+    pub fn expected(&self) -> String {
+        let mut out = String::new();
+        for (part, answer) in &self.answers {
+            if answer.contains('\n') {
+                out.push_str(&format!("part{}:\n{}\n", part, answer));
+            } else {
+                out.push_str(&format!("part{}: {}\n", part, answer));
+            }
+        }
+        out
+    }
+}
+
+pub fn default_page(root: &Path, day: u32) -> PathBuf {
+    root.join(format!("inputs/day{}.html", day))
+}
+
+fn decode(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    decode(&text)
+}
+
+/// The contents of every `open ... close` section of `html`, in order.
+fn sections<'a>(html: &'a str, open: &'a str, close: &'a str) -> impl Iterator<Item = &'a str> {
+    html.split(open).skip(1).filter_map(move |rest| {
+        let (_, rest) = rest.split_once('>')?;
+        rest.split_once(close).map(|(inner, _)| inner)
+    })
+}
+
+pub fn parse_part(article: &str) -> Part {
+    let blocks = sections(article, "<pre", "</pre>")
+        .map(|pre| strip_tags(pre).trim_end_matches('\n').to_string())
+        .collect();
+    let outside_pre: String = article
+        .split("<pre")
+        .enumerate()
+        .map(|(i, piece)| match i {
+            0 => piece,
+            _ => piece.split_once("</pre>").map_or("", |(_, after)| after),
+        })
+        .collect();
+    let answers = sections(&outside_pre, "<code", "</code>")
+        .filter(|code| code.trim_start().starts_with("<em"))
+        .map(|code| strip_tags(code).trim().to_string())
+        .collect();
+    Part { blocks, answers }
+}
+
+pub fn parse_page(html: &str) -> Vec<Part> {
+    sections(html, "<article", "</article>")
+        .map(parse_part)
+        .collect()
+}
+
+/// Example files for the parts of a page, part 2 joining part 1's example
+/// when it shows no new input.
+pub fn propose(parts: &[Part]) -> Vec<Example> {
+    let mut examples: Vec<Example> = Vec::new();
+    for (part, desc) in (1..).zip(parts) {
+        let answer = desc.answers.last();
+        let input = desc
+            .blocks
+            .first()
+            .or_else(|| examples.last().map(|e| &e.input));
+        let (input, answer) = match (input, answer) {
+            (Some(input), Some(answer)) => (input.clone(), answer.clone()),
+            _ => continue,
+        };
+        match examples.iter_mut().find(|e| e.input == input) {
+            Some(example) => {
+                example.answers.insert(part, answer);
+            }
+            None => {
+                let name = match examples.len() {
+                    0 => String::from("example"),
+                    n => format!("example{}", n + 1),
+                };
+                examples.push(Example {
+                    name,
+                    input,
+                    answers: BTreeMap::from([(part, answer)]),
+                });
+            }
+        }
+    }
+    examples
+}
+
+/// Shows the files for `examples` under `dir` and writes the new ones once
+/// the user agrees; existing files are never replaced.
+pub fn confirm_and_write<R: BufRead, W: Write>(
+    dir: &Path,
+    examples: &[Example],
+    assume_yes: bool,
+    mut input: R,
+    mut output: W,
+) -> Result<Vec<PathBuf>, Error> {
+    let stdin = |e| Error::from(fs::IoError::new("<stdin>", e));
+    let stdout = |e| Error::from(fs::IoError::new("<stdout>", e));
+    let mut files = Vec::new();
+    for example in examples {
+        for (path, contents) in [
+            (
+                dir.join(format!("{}.txt", example.name)),
+                example.input.clone() + "\n",
+            ),
+            (
+                dir.join(format!("{}.out", example.name)),
+                example.expected(),
+            ),
+        ] {
+            if path.exists() {
+                writeln!(output, "{} exists, skipping", path.display()).map_err(stdout)?;
+                continue;
+            }
+            writeln!(output, "--- {}", path.display()).map_err(stdout)?;
+            write!(output, "{}", contents).map_err(stdout)?;
+            files.push((path, contents));
+        }
+    }
+    if files.is_empty() {
+        writeln!(output, "nothing to write").map_err(stdout)?;
+        return Ok(Vec::new());
+    }
+    if !assume_yes {
+        write!(output, "write {} files? [y/N] ", files.len()).map_err(stdout)?;
+        output.flush().map_err(stdout)?;
+        let mut reply = String::new();
+        input.read_line(&mut reply).map_err(stdin)?;
+        if !matches!(reply.trim(), "y" | "Y" | "yes") {
+            return Ok(Vec::new());
+        }
+    }
+    for (path, contents) in &files {
+        fs::write_output(path, contents)?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Report Repair ---</h2>
+<p>For example, suppose your expense report contained the following:</p>
+<pre><code>1721
+979
+366
+</code></pre>
+<p>Multiplying them together produces <code>1721 * 299 = <em>514579</em></code>, so the correct answer is <code><em>514579</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1014624</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Using the above example again, the product is <code><em>241861950</em></code>.</p>
+</article>
+</main></body></html>
+"#;
+
+    #[test]
+    fn parts() {
+        let parts = parse_page(PAGE);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].blocks, vec!["1721\n979\n366"]);
+        assert_eq!(parts[0].answers, vec!["514579"]);
+        assert_eq!(
+            parts[1],
+            Part {
+                blocks: vec![],
+                answers: vec![String::from("241861950")]
+            }
+        );
+        let part = parse_part(
+            "<pre><code>a &lt;<em>b</em>&gt; &amp;c</code></pre><code><em>x</em></code>",
+        );
+        assert_eq!(part.blocks, vec!["a <b> &c"]);
+        assert_eq!(part.answers, vec!["x"]);
+    }
+
+    #[test]
+    fn proposals() {
+        let examples = propose(&parse_page(PAGE));
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].name, "example");
+        assert_eq!(examples[0].expected(), "part1: 514579\npart2: 241861950\n");
+
+        let parts = [
+            Part {
+                blocks: vec![String::from("a")],
+                answers: vec![String::from("1")],
+            },
+            Part {
+                blocks: vec![String::from("b")],
+                answers: vec![String::from("#.\n.#")],
+            },
+        ];
+        let examples = propose(&parts);
+        assert_eq!(examples[1].name, "example2");
+        assert_eq!(examples[1].expected(), "part2:\n#.\n.#\n");
+    }
+
+    #[test]
+    fn confirmation() {
+        let dir = std::env::temp_dir().join(format!("aoc-extract-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let examples = propose(&parse_page(PAGE));
+
+        let mut shown = Vec::new();
+        let written = confirm_and_write(&dir, &examples, false, &b"n\n"[..], &mut shown).unwrap();
+        assert!(written.is_empty());
+        assert!(!dir.exists());
+        let shown = String::from_utf8(shown).unwrap();
+        assert!(
+            shown.contains("example.out\npart1: 514579\npart2: 241861950\nwrite 2 files? [y/N] ")
+        );
+
+        let written = confirm_and_write(&dir, &examples, false, &b"y\n"[..], Vec::new()).unwrap();
+        assert_eq!(written.len(), 2);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("example.txt")).unwrap(),
+            "1721\n979\n366\n"
+        );
+        let written = confirm_and_write(&dir, &examples, true, &b""[..], Vec::new()).unwrap();
+        assert!(written.is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    struct ClosedPipe;
+
+    impl Write for ClosedPipe {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn broken_pipe_is_an_io_error() {
+        let examples = propose(&parse_page(PAGE));
+        let dir = std::env::temp_dir().join("aoc-extract-unwritten");
+        let err = confirm_and_write(&dir, &examples, true, &b""[..], ClosedPipe).unwrap_err();
+        assert!(matches!(err, Error::Io(_)), "{:?}", err);
+        assert!(err.to_string().starts_with("<stdout>: "));
+    }
+}
@@ -0,0 +1,138 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::str::FromStr;
+
+use super::Error;
+
+#[derive(Debug, thiserror::Error)]
+#[error("{path}: {source}")]
+pub struct IoError {
+    path: PathBuf,
+    #[source]
+    source: io::Error,
+}
+
+impl IoError {
+    /// An error reading or writing `path`, which may name a stream such as
+    /// `<stdin>` rather than a file.
+    pub fn new<P: Into<PathBuf>>(path: P, source: io::Error) -> Self {
+        IoError {
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+trait WithContext<C, T, E> {
+    fn with_context<CC: Into<C>>(self, ctx: CC) -> Result<T, E>;
+}
+
+impl<T> WithContext<PathBuf, T, IoError> for Result<T, io::Error> {
+    fn with_context<CC: Into<PathBuf>>(self, ctx: CC) -> Result<T, IoError> {
+        self.map_err(|e| IoError {
+            path: ctx.into(),
+            source: e,
+        })
+    }
+}
+
+pub fn read_input<P: AsRef<Path>>(path: P) -> Result<String, Error> {
+    Ok(std::fs::read_to_string(&path).with_context(path.as_ref())?)
+}
+
+pub fn read_optional<P: AsRef<Path>>(path: P) -> Result<Option<String>, Error> {
+    match std::fs::read_to_string(&path) {
+        Ok(text) => Ok(Some(text)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(source) => Err(IoError {
+            path: path.as_ref().into(),
+            source,
+        }
+        .into()),
+    }
+}
+
+pub fn write_output<P: AsRef<Path>>(path: P, contents: &str) -> Result<(), Error> {
+    let path = path.as_ref();
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir).with_context(dir)?;
+    }
+    Ok(std::fs::write(path, contents).with_context(path)?)
+}
+
+/// The entries of `dir` in sorted order, or none if it does not exist.
+pub fn list_dir<P: AsRef<Path>>(dir: P) -> Result<Vec<PathBuf>, Error> {
+    let dir = dir.as_ref();
+    let entries = match std::fs::read_dir(dir) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        entries => entries.with_context(dir)?,
+    };
+    let mut paths = entries
+        .map(|e| e.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(dir)?;
+    paths.sort();
+    Ok(paths)
+}
+
+/// Runs `command` to completion, capturing its stdout.
+pub fn command_output(command: &mut Command) -> Result<Output, Error> {
+    let program = PathBuf::from(command.get_program());
+    Ok(command.output().with_context(program)?)
+}
+
+/// Parses each piece of `input` split on `pat`. A failure keeps the piece's
+/// own error, prefixed with the line the piece starts on.
+pub fn parse_str<R>(input: &str, pat: &str) -> Result<Vec<R>, Error>
+where
+    R: FromStr,
+    R::Err: fmt::Display,
+{
+    let skipped = input.len() - input.trim_start().len();
+    let mut line = 1 + input[..skipped].matches('\n').count();
+    input
+        .trim()
+        .split(pat)
+        .map(|v| {
+            let at = line;
+            line += v.matches('\n').count() + pat.matches('\n').count();
+            R::from_str(v).map_err(|e| {
+                if v.contains('\n') {
+                    Error::Parse(format!("line {}: {}", at, e))
+                } else {
+                    Error::Parse(format!("line {}: `{}`: {}", at, v, e))
+                }
+            })
+        })
+        .collect()
+}
+
+pub fn parse_input<R, P>(path: P, pat: &str) -> Result<Vec<R>, Error>
+where
+    R: FromStr,
+    R::Err: fmt::Display,
+    P: AsRef<Path>,
+{
+    parse_str(&read_input(path)?, pat)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors_name_the_line() {
+        assert_eq!(parse_str::<u32>("\n1\n2\n", "\n").unwrap(), [1, 2]);
+        let err = parse_str::<u32>("\n1\n2\nx3\n", "\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "error: line 4: `x3`: invalid digit found in string"
+        );
+        let err = parse_str::<u32>("1\n2\n\n3\n4", "\n\n").unwrap_err();
+        assert!(err.to_string().starts_with("error: line 1: "), "{}", err);
+        let err = parse_str::<u32>("1\n\n2\n3", "\n\n").unwrap_err();
+        assert!(err.to_string().starts_with("error: line 3: "), "{}", err);
+    }
+}
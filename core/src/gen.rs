@@ -0,0 +1,83 @@
+//! Seeded randomness and options for the per-year input generators.
+
+/// SplitMix64: tiny, fast and stable across releases, so a seed always
+/// reproduces the same input.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `lo..=hi`.
+    pub fn range(&mut self, lo: usize, hi: usize) -> usize {
+        assert!(lo <= hi, "empty range {}..={}", lo, hi);
+        let span = (hi - lo) as u128 + 1;
+        lo + ((self.next_u64() as u128 * span) >> 64) as usize
+    }
+
+    pub fn chance(&mut self, p: f64) -> bool {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 <= p
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0, items.len() - 1)]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0, i));
+        }
+    }
+
+    /// `len` characters drawn from `alphabet`.
+    pub fn letters(&mut self, alphabet: &[u8], len: usize) -> String {
+        (0..len).map(|_| *self.pick(alphabet) as char).collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    /// Number of records (lines, passports, groups, grid rows...).
+    pub size: usize,
+    /// Grid width, for map-shaped inputs.
+    pub width: usize,
+    /// Fraction of records that should be valid, where the puzzle has such a notion.
+    pub valid: f64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            size: 200,
+            width: 31,
+            valid: 0.5,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded() {
+        let a: Vec<u64> = (0..4).map(|_| Rng::new(7).next_u64()).collect();
+        assert!(a.iter().all(|&v| v == a[0]));
+        assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+        let mut rng = Rng::new(1);
+        assert!((0..1000)
+            .map(|_| rng.range(3, 5))
+            .all(|v| (3..=5).contains(&v)));
+        assert_eq!(Rng::new(2).letters(b"ab", 6).len(), 6);
+    }
+}
@@ -0,0 +1,300 @@
+//! Directed graphs with weighted edges between string-labelled nodes.
+//!
+//! Labels are interned on first use, so nodes are addressed by a dense
+//! [`NodeId`] and every edge is stored both forwards and backwards.
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum GraphError {
+    #[error("unknown node `{0}`")]
+    Unknown(String),
+    #[error("cycle {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+    #[error("weighted total of `{0}` overflows")]
+    Overflow(String),
+}
+
+pub type NodeId = usize;
+
+#[derive(Debug, Clone)]
+pub struct Graph<W> {
+    labels: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    edges: Vec<Vec<(NodeId, W)>>,
+    reverse: Vec<Vec<(NodeId, W)>>,
+}
+
+impl<W> Default for Graph<W> {
+    fn default() -> Self {
+        Graph {
+            labels: Vec::new(),
+            ids: HashMap::new(),
+            edges: Vec::new(),
+            reverse: Vec::new(),
+        }
+    }
+}
+
+impl<W> Graph<W> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The id of `label`, adding the node on first use.
+    pub fn intern(&mut self, label: &str) -> NodeId {
+        if let Some(&id) = self.ids.get(label) {
+            return id;
+        }
+        let id = self.labels.len();
+        self.labels.push(String::from(label));
+        self.ids.insert(String::from(label), id);
+        self.edges.push(Vec::new());
+        self.reverse.push(Vec::new());
+        id
+    }
+
+    pub fn id(&self, label: &str) -> Result<NodeId, GraphError> {
+        self.ids
+            .get(label)
+            .copied()
+            .ok_or_else(|| GraphError::Unknown(String::from(label)))
+    }
+
+    pub fn label(&self, id: NodeId) -> &str {
+        &self.labels[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Outgoing edges of `id` with their weights.
+    pub fn edges(&self, id: NodeId) -> &[(NodeId, W)] {
+        &self.edges[id]
+    }
+
+    /// Incoming edges of `id`, each given by its source.
+    pub fn incoming(&self, id: NodeId) -> &[(NodeId, W)] {
+        &self.reverse[id]
+    }
+
+    /// Nodes reached from `start` through at least one edge; `start` itself
+    /// is only included when it lies on a cycle.
+    pub fn reachable(&self, start: NodeId) -> Vec<NodeId> {
+        search(&self.edges, start)
+    }
+
+    /// Nodes that reach `start`, i.e. [`reachable`](Graph::reachable) against
+    /// the reversed edges.
+    pub fn reachable_from(&self, start: NodeId) -> Vec<NodeId> {
+        search(&self.reverse, start)
+    }
+
+    /// Some cycle as the nodes along it, the first repeated at the end.
+    pub fn find_cycle(&self) -> Option<Vec<NodeId>> {
+        let mut state = vec![Visit::New; self.len()];
+        for root in 0..self.len() {
+            if state[root] != Visit::New {
+                continue;
+            }
+            let mut stack = vec![(root, 0)];
+            state[root] = Visit::Open;
+            while let Some(&mut (node, ref mut next)) = stack.last_mut() {
+                let Some(&(child, _)) = self.edges[node].get(*next) else {
+                    state[node] = Visit::Done;
+                    stack.pop();
+                    continue;
+                };
+                *next += 1;
+                match state[child] {
+                    Visit::New => {
+                        state[child] = Visit::Open;
+                        stack.push((child, 0));
+                    }
+                    Visit::Open => {
+                        let start = stack.iter().position(|&(n, _)| n == child)?;
+                        let mut cycle: Vec<_> = stack[start..].iter().map(|&(n, _)| n).collect();
+                        cycle.push(child);
+                        return Some(cycle);
+                    }
+                    Visit::Done => {}
+                }
+            }
+        }
+        None
+    }
+
+    /// Every node, each before all of its successors. Ties are broken by
+    /// insertion order so the result is stable.
+    pub fn topological_order(&self) -> Result<Vec<NodeId>, GraphError> {
+        let mut pending: Vec<usize> = self.reverse.iter().map(Vec::len).collect();
+        let mut ready: Vec<NodeId> = (0..self.len()).rev().filter(|&n| pending[n] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(node) = ready.pop() {
+            order.push(node);
+            for &(child, _) in self.edges[node].iter().rev() {
+                pending[child] -= 1;
+                if pending[child] == 0 {
+                    ready.push(child);
+                }
+            }
+        }
+        if order.len() < self.len() {
+            return Err(self.cycle_error());
+        }
+        Ok(order)
+    }
+
+    fn cycle_error(&self) -> GraphError {
+        let cycle = self.find_cycle().unwrap_or_default();
+        GraphError::Cycle(cycle.into_iter().map(|n| self.labels[n].clone()).collect())
+    }
+}
+
+impl<W: Clone> Graph<W> {
+    pub fn add_edge(&mut self, from: &str, to: &str, weight: W) {
+        let (from, to) = (self.intern(from), self.intern(to));
+        self.edges[from].push((to, weight.clone()));
+        self.reverse[to].push((from, weight));
+    }
+}
+
+impl<W: Copy + Into<u64>> Graph<W> {
+    /// How many nodes lie below `start` when an edge of weight `w` stands for
+    /// `w` copies of its target: the sum over every path leaving `start` of
+    /// the product of its weights. Shared subgraphs are only walked once.
+    pub fn weighted_total(&self, start: NodeId) -> Result<u64, GraphError> {
+        let mut totals: Vec<Option<u64>> = vec![None; self.len()];
+        let mut open = vec![false; self.len()];
+        let mut stack = vec![start];
+        open[start] = true;
+        while let Some(&node) = stack.last() {
+            let mut total = Some(0u64);
+            let mut waiting = false;
+            for &(child, weight) in &self.edges[node] {
+                match totals[child] {
+                    Some(below) => {
+                        total = total.and_then(|t| {
+                            below
+                                .checked_add(1)?
+                                .checked_mul(weight.into())?
+                                .checked_add(t)
+                        });
+                    }
+                    None if open[child] => return Err(self.cycle_error()),
+                    None => {
+                        open[child] = true;
+                        stack.push(child);
+                        waiting = true;
+                        break;
+                    }
+                }
+            }
+            if waiting {
+                continue;
+            }
+            let total = total.ok_or_else(|| GraphError::Overflow(self.labels[node].clone()))?;
+            totals[node] = Some(total);
+            open[node] = false;
+            stack.pop();
+        }
+        Ok(totals[start].unwrap_or(0))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Visit {
+    New,
+    Open,
+    Done,
+}
+
+fn search<W>(adjacency: &[Vec<(NodeId, W)>], start: NodeId) -> Vec<NodeId> {
+    let mut seen = vec![false; adjacency.len()];
+    let mut found = Vec::new();
+    let mut stack: Vec<NodeId> = adjacency[start].iter().map(|&(n, _)| n).collect();
+    while let Some(node) = stack.pop() {
+        if std::mem::replace(&mut seen[node], true) {
+            continue;
+        }
+        found.push(node);
+        stack.extend(adjacency[node].iter().map(|&(n, _)| n));
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &str, u32)]) -> Graph<u32> {
+        let mut graph = Graph::new();
+        for &(from, to, weight) in edges {
+            graph.add_edge(from, to, weight);
+        }
+        graph
+    }
+
+    fn labels(graph: &Graph<u32>, mut ids: Vec<NodeId>) -> Vec<&str> {
+        ids.sort_unstable();
+        ids.into_iter().map(|n| graph.label(n)).collect()
+    }
+
+    #[test]
+    fn reachability() {
+        let g = graph(&[("a", "b", 1), ("b", "c", 1), ("d", "c", 1), ("c", "e", 1)]);
+        let c = g.id("c").unwrap();
+        assert_eq!(labels(&g, g.reachable(c)), ["e"]);
+        assert_eq!(labels(&g, g.reachable_from(c)), ["a", "b", "d"]);
+        assert_eq!(labels(&g, g.reachable(g.id("a").unwrap())), ["b", "c", "e"]);
+        assert_eq!(g.id("x"), Err(GraphError::Unknown(String::from("x"))));
+    }
+
+    #[test]
+    fn topological_order() {
+        let g = graph(&[("a", "c", 1), ("b", "c", 1), ("c", "d", 1), ("a", "d", 1)]);
+        let order = g.topological_order().unwrap();
+        let names: Vec<_> = order.iter().map(|&n| g.label(n)).collect();
+        assert_eq!(names, ["a", "b", "c", "d"]);
+        assert_eq!(g.find_cycle(), None);
+    }
+
+    #[test]
+    fn cycles() {
+        let g = graph(&[("a", "b", 1), ("b", "c", 1), ("c", "b", 1), ("c", "d", 1)]);
+        let cycle = g.find_cycle().unwrap();
+        let names: Vec<_> = cycle.iter().map(|&n| g.label(n)).collect();
+        assert_eq!(names, ["b", "c", "b"]);
+        let err = g.topological_order().unwrap_err();
+        assert_eq!(err.to_string(), "cycle b -> c -> b");
+        assert!(g.weighted_total(g.id("a").unwrap()).is_err());
+        assert_eq!(g.weighted_total(g.id("d").unwrap()), Ok(0));
+        assert!(g
+            .reachable(g.id("b").unwrap())
+            .contains(&g.id("b").unwrap()));
+    }
+
+    #[test]
+    fn weighted_totals() {
+        // a holds 2 b and 3 c; b holds 4 c; c is empty.
+        let g = graph(&[("a", "b", 2), ("a", "c", 3), ("b", "c", 4)]);
+        assert_eq!(g.weighted_total(g.id("c").unwrap()), Ok(0));
+        assert_eq!(g.weighted_total(g.id("b").unwrap()), Ok(4));
+        assert_eq!(g.weighted_total(g.id("a").unwrap()), Ok(2 * 5 + 3));
+
+        let deep = graph(&[
+            ("a", "b", u32::MAX),
+            ("b", "c", u32::MAX),
+            ("c", "d", u32::MAX),
+        ]);
+        assert_eq!(
+            deep.weighted_total(deep.id("a").unwrap()),
+            Err(GraphError::Overflow(String::from("a")))
+        );
+    }
+}
@@ -0,0 +1,148 @@
+//! Rectangular grids of cells, as drawn by most map-shaped puzzle inputs.
+
+use crate::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid from `cells` in row-major order.
+    pub fn new(width: usize, cells: Vec<T>) -> Result<Self, Error> {
+        if width == 0 || !cells.len().is_multiple_of(width) {
+            return Err(Error::Parse(format!(
+                "{} cells do not fill rows of {}",
+                cells.len(),
+                width
+            )));
+        }
+        let height = cells.len() / width;
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Reads one row per line, mapping every character with `cell`. All rows
+    /// must have the same length.
+    pub fn parse<F>(input: &str, cell: F) -> Result<Self, Error>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        let mut width = None;
+        let mut cells = Vec::new();
+        for (y, line) in input.trim_end().lines().enumerate() {
+            let before = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                let value = cell(c).ok_or_else(|| {
+                    Error::Parse(format!(
+                        "line {}, column {}: unexpected `{}`",
+                        y + 1,
+                        x + 1,
+                        c
+                    ))
+                })?;
+                cells.push(value);
+            }
+            let len = cells.len() - before;
+            match width {
+                None => width = Some(len),
+                Some(w) if w != len => {
+                    return Err(Error::Parse(format!(
+                        "line {}: expected {} cells, found {}",
+                        y + 1,
+                        w,
+                        len
+                    )))
+                }
+                Some(_) => {}
+            }
+        }
+        Self::new(width.unwrap_or(0), cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    /// Every `(x, y)` in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trees(c: char) -> Option<bool> {
+        match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let grid = Grid::parse("..#\n#..\n", trees).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(2, 0), Some(&true));
+        assert_eq!(grid.get(0, 1), Some(&true));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+        assert_eq!(grid.rows().nth(1), Some(&[true, false, false][..]));
+        assert_eq!(grid.positions().nth(4), Some((1, 1)));
+    }
+
+    #[test]
+    fn invalid() {
+        let err = Grid::parse("..#\n#.\n", trees).unwrap_err();
+        assert_eq!(err.to_string(), "error: line 2: expected 3 cells, found 2");
+        let err = Grid::parse("..#\n#.x\n", trees).unwrap_err();
+        assert_eq!(err.to_string(), "error: line 2, column 3: unexpected `x`");
+        assert!(Grid::parse("", trees).is_err());
+        assert!(Grid::new(2, vec![1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn update() {
+        let mut grid = Grid::new(2, vec![0; 4]).unwrap();
+        *grid.get_mut(1, 1).unwrap() = 5;
+        assert_eq!(grid.cells(), &[0, 0, 0, 5]);
+        assert!(grid.get_mut(2, 0).is_none());
+    }
+}
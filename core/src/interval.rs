@@ -0,0 +1,165 @@
+//! Inclusive integer intervals and sets of them, kept merged.
+
+use std::fmt;
+
+/// Integers, whose intervals merge when they touch as well as overlap.
+pub trait Discrete: Copy + Ord {
+    fn successor(self) -> Option<Self>;
+}
+
+macro_rules! discrete {
+    ($($t:ty),*) => {$(
+        impl Discrete for $t {
+            fn successor(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+        }
+    )*};
+}
+
+discrete!(u8, u16, u32, u64, usize, i32, i64);
+
+/// The values from `lo` up to and including `hi`; empty if `lo > hi`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub lo: T,
+    pub hi: T,
+}
+
+impl<T: Discrete> Interval<T> {
+    pub const fn new(lo: T, hi: T) -> Self {
+        Interval { lo, hi }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.lo <= value && value <= self.hi
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lo > self.hi
+    }
+
+    /// Whether `other` starts after this interval ends with a gap between.
+    fn is_before(&self, other: &Self) -> bool {
+        self.hi.successor().is_some_and(|next| next < other.lo)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.lo, self.hi)
+    }
+}
+
+/// A union of intervals, stored sorted with overlapping and touching ones
+/// merged.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `interval`, merging it with every interval it overlaps or touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        let start = self.intervals.partition_point(|i| i.is_before(&interval));
+        let end = self.intervals.partition_point(|i| !interval.is_before(i));
+        let mut merged = interval;
+        if start < end {
+            merged.lo = merged.lo.min(self.intervals[start].lo);
+            merged.hi = merged.hi.max(self.intervals[end - 1].hi);
+        }
+        self.intervals.splice(start..end, [merged]);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        set.extend(other.intervals.iter().copied());
+        set
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let at = self.intervals.partition_point(|i| i.hi < value);
+        self.intervals.get(at).is_some_and(|i| i.contains(value))
+    }
+
+    /// The merged intervals in ascending order.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+}
+
+impl<T: Discrete> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, intervals: I) {
+        for interval in intervals {
+            self.insert(interval);
+        }
+    }
+}
+
+impl<T: Discrete> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(intervals: I) -> Self {
+        let mut set = Self::new();
+        set.extend(intervals);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intervals() {
+        let i = Interval::new(3, 7);
+        assert!(i.contains(3) && i.contains(7) && !i.contains(8));
+        assert!(Interval::new(4, 3).is_empty());
+        assert_eq!(i.to_string(), "3-7");
+    }
+
+    #[test]
+    fn merging() {
+        let mut set: IntervalSet<u32> = [Interval::new(1, 3), Interval::new(5, 7)]
+            .into_iter()
+            .collect();
+        assert_eq!(set.intervals().len(), 2);
+        assert!(set.contains(2) && !set.contains(4) && set.contains(5));
+        set.insert(Interval::new(4, 4));
+        assert_eq!(set.intervals(), [Interval::new(1, 7)]);
+        set.insert(Interval::new(10, 12));
+        set.insert(Interval::new(20, 20));
+        set.insert(Interval::new(9, 0));
+        assert_eq!(set.intervals().len(), 3);
+        set.insert(Interval::new(8, 19));
+        assert_eq!(set.intervals(), [Interval::new(1, 20)]);
+
+        let other: IntervalSet<u32> = [Interval::new(0, 0), Interval::new(30, u32::MAX)]
+            .into_iter()
+            .collect();
+        let all = set.union(&other);
+        assert_eq!(
+            all.intervals(),
+            [Interval::new(0, 20), Interval::new(30, u32::MAX)]
+        );
+        assert!(all.contains(u32::MAX) && !all.contains(25));
+        assert!(IntervalSet::<u8>::new().is_empty());
+    }
+}
@@ -0,0 +1,60 @@
+//! Shared library for every event year: input handling, parsing, grids,
+//! graphs, combinators and the puzzle runner.
+
+pub mod answer;
+pub mod answers;
+pub mod automaton;
+pub mod bench;
+pub mod bits;
+pub mod combinator;
+pub mod constraint;
+pub mod dp;
+pub mod extract;
+pub mod fs;
+pub mod gen;
+pub mod graph;
+pub mod grid;
+pub mod interval;
+pub mod num;
+pub mod ocr;
+pub mod parser;
+pub mod point;
+pub mod prop;
+pub mod record;
+pub mod runner;
+pub mod scaffold;
+pub mod sequence;
+pub mod submit;
+pub mod sums;
+pub mod vm;
+pub mod watch;
+
+pub use answer::Answer;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] fs::IoError),
+    #[error("error: {0}")]
+    Parse(String),
+    #[error("syntax error {0}")]
+    Syntax(#[from] parser::ParseError),
+    #[error(transparent)]
+    Record(#[from] record::RecordError),
+    #[error(transparent)]
+    Scaffold(#[from] scaffold::ScaffoldError),
+    #[error(transparent)]
+    Submit(#[from] submit::SubmitError),
+    #[error(transparent)]
+    Ocr(#[from] ocr::OcrError),
+    #[error(transparent)]
+    Graph(#[from] graph::GraphError),
+    #[error(transparent)]
+    Dp(#[from] dp::DpError),
+    #[error(transparent)]
+    Num(#[from] num::NumError),
+    #[error(transparent)]
+    Bits(#[from] bits::BitsError),
+    #[error(transparent)]
+    Constraint(#[from] constraint::ConstraintError),
+}
@@ -0,0 +1,165 @@
+//! Number theory over `i128`: gcd, lcm, modular arithmetic and the Chinese
+//! Remainder Theorem.
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum NumError {
+    #[error("modulus must be positive, found {0}")]
+    Modulus(i128),
+    #[error("{0} has no inverse modulo {1}")]
+    NoInverse(i128, i128),
+    #[error("moduli {0} and {1} are not coprime")]
+    NotCoprime(i128, i128),
+    #[error("combined modulus overflows i128")]
+    Overflow,
+}
+
+/// Greatest common divisor, never negative.
+pub fn gcd(a: i128, b: i128) -> i128 {
+    extended_gcd(a, b).0
+}
+
+/// `(g, x, y)` with `a * x + b * y == g`, `g` being the gcd of `a` and `b`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// Least common multiple, never negative, or `None` if it overflows.
+pub fn lcm(a: i128, b: i128) -> Option<i128> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b).map(i128::abs)
+}
+
+fn check_modulus(m: i128) -> Result<(), NumError> {
+    if m > 0 {
+        Ok(())
+    } else {
+        Err(NumError::Modulus(m))
+    }
+}
+
+/// `a * b mod m` in `0..m`, doubling and adding in `u128` when the product
+/// overflows: both stay below `m`, so their sum always fits.
+fn mul_mod(a: i128, b: i128, m: i128) -> i128 {
+    let (a, b) = (a.rem_euclid(m), b.rem_euclid(m));
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+    let (mut a, mut b, m) = (a as u128, b as u128, m as u128);
+    let mut result = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = (result + a) % m;
+        }
+        a = (a + a) % m;
+        b >>= 1;
+    }
+    result as i128
+}
+
+/// `base^exp mod m` in `0..m`.
+pub fn mod_pow(base: i128, mut exp: u128, m: i128) -> Result<i128, NumError> {
+    check_modulus(m)?;
+    let (mut base, mut result) = (base.rem_euclid(m), 1 % m);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    Ok(result)
+}
+
+/// The `x` in `0..m` with `a * x ≡ 1 (mod m)`.
+pub fn mod_inverse(a: i128, m: i128) -> Result<i128, NumError> {
+    check_modulus(m)?;
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    if g != 1 {
+        return Err(NumError::NoInverse(a, m));
+    }
+    Ok(x.rem_euclid(m))
+}
+
+/// The `x` in `0..M` satisfying every `x ≡ residue (mod modulus)`, and `M`,
+/// the product of the moduli. The moduli must be pairwise coprime.
+pub fn crt(congruences: &[(i128, i128)]) -> Result<(i128, i128), NumError> {
+    let (mut x, mut modulus) = (0, 1);
+    for (i, &(residue, m)) in congruences.iter().enumerate() {
+        check_modulus(m)?;
+        if let Some(&(_, other)) = congruences[..i].iter().find(|&&(_, o)| gcd(o, m) != 1) {
+            return Err(NumError::NotCoprime(other, m));
+        }
+        // x + modulus * k ≡ residue (mod m)
+        let target = residue.rem_euclid(m) - x % m;
+        let k = mul_mod(target, mod_inverse(modulus, m)?, m);
+        let combined = modulus.checked_mul(m).ok_or(NumError::Overflow)?;
+        x = modulus
+            .checked_mul(k)
+            .and_then(|step| step.checked_add(x))
+            .ok_or(NumError::Overflow)?;
+        modulus = combined;
+    }
+    Ok((x, modulus))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn divisors() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!((g, 240 * x + 46 * y), (2, 2));
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(-4, 6), Some(12));
+        assert_eq!(lcm(i128::MAX, 2), None);
+    }
+
+    #[test]
+    fn modular() {
+        assert_eq!(mod_pow(4, 13, 497), Ok(445));
+        assert_eq!(mod_pow(-2, 3, 5), Ok(2));
+        assert_eq!(mod_pow(3, 0, 1), Ok(0));
+        let big = (1 << 100) + 277;
+        assert_eq!(mod_pow(big - 1, 2, big), Ok(1));
+        let huge = (1 << 126) + 15;
+        assert_eq!(mod_pow(huge - 2, 3, huge), Ok(huge - 8));
+        assert_eq!(mod_pow(i128::MAX - 2, 3, i128::MAX), Ok(i128::MAX - 8));
+        assert_eq!(mod_inverse(3, 11), Ok(4));
+        assert_eq!(mod_inverse(-3, 11), Ok(7));
+        assert_eq!(mod_inverse(6, 9), Err(NumError::NoInverse(6, 9)));
+        assert_eq!(mod_inverse(1, 0), Err(NumError::Modulus(0)));
+    }
+
+    #[test]
+    fn chinese_remainder() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+        assert_eq!(crt(&[(-1, 4), (0, 9)]), Ok((27, 36)));
+        assert_eq!(crt(&[]), Ok((0, 1)));
+        assert_eq!(crt(&[(1, 6), (2, 9)]), Err(NumError::NotCoprime(6, 9)));
+        assert_eq!(crt(&[(0, 5), (1, -3)]), Err(NumError::Modulus(-3)));
+        let huge = 1 << 70;
+        assert_eq!(crt(&[(0, huge), (1, huge - 1)]), Err(NumError::Overflow));
+        let huge = (1 << 126) + 15;
+        assert_eq!(crt(&[(1, 3), (huge - 5, huge)]), Err(NumError::Overflow));
+        assert_eq!(crt(&[(huge - 5, huge), (0, 1)]), Ok((huge - 5, huge)));
+    }
+}
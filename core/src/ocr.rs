@@ -0,0 +1,257 @@
+//! Reads the block letters some puzzles draw with `#` and `.`.
+//!
+//! Both fonts used by the puzzles are supported: 6 rows high (letters four
+//! columns wide) and 10 rows high (six columns wide). Glyphs are separated by
+//! blank columns, so letters of any width are found without knowing the
+//! spacing up front.
+
+use std::fmt;
+
+const FONT_6: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const FONT_10: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum OcrError {
+    #[error("no letters found")]
+    Empty,
+    #[error("letters must be 6 or 10 rows high, found {0}")]
+    Height(usize),
+    #[error("unrecognised glyphs at columns {}", Spans(.0))]
+    Unrecognised(Vec<(usize, usize)>),
+}
+
+struct Spans<'a>(&'a [(usize, usize)]);
+
+impl fmt::Display for Spans<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (start, end)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}-{}", start, end)?;
+        }
+        Ok(())
+    }
+}
+
+fn is_lit(c: char) -> bool {
+    matches!(c, '#' | '█')
+}
+
+/// Lit cells of each column, one bitmask per column with row 0 as bit 0.
+fn columns(rows: &[Vec<bool>]) -> Vec<u16> {
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    (0..width)
+        .map(|x| {
+            rows.iter()
+                .enumerate()
+                .filter(|(_, row)| row.get(x).copied().unwrap_or(false))
+                .fold(0, |mask, (y, _)| mask | 1 << y)
+        })
+        .collect()
+}
+
+/// Splits columns into runs of non-blank ones: `(start, end)` inclusive.
+fn glyph_spans(columns: &[u16]) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (x, &col) in columns.iter().chain(Some(&0)).enumerate() {
+        match (col != 0, start) {
+            (true, None) => start = Some(x),
+            (false, Some(s)) => {
+                spans.push((s, x - 1));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    spans
+}
+
+fn lookup(font: &[(char, &str)], glyph: &[u16]) -> Option<char> {
+    font.iter().find_map(|(letter, pattern)| {
+        let rows: Vec<Vec<bool>> = pattern
+            .lines()
+            .map(|l| l.chars().map(is_lit).collect())
+            .collect();
+        let cols = columns(&rows);
+        let first = cols.iter().position(|&c| c != 0)?;
+        let last = cols.iter().rposition(|&c| c != 0)?;
+        (cols[first..=last] == *glyph).then_some(*letter)
+    })
+}
+
+/// Recognises letters drawn as rows of lit and unlit cells.
+pub fn recognize_cells(rows: &[Vec<bool>]) -> Result<String, OcrError> {
+    let first = rows.iter().position(|r| r.contains(&true));
+    let last = rows.iter().rposition(|r| r.contains(&true));
+    let rows = match (first, last) {
+        (Some(first), Some(last)) => &rows[first..=last],
+        _ => return Err(OcrError::Empty),
+    };
+    let font = match rows.len() {
+        6 => FONT_6,
+        10 => FONT_10,
+        height => return Err(OcrError::Height(height)),
+    };
+
+    let cols = columns(rows);
+    let mut text = String::new();
+    let mut unknown = Vec::new();
+    for (start, end) in glyph_spans(&cols) {
+        match lookup(font, &cols[start..=end]) {
+            Some(letter) => text.push(letter),
+            None => unknown.push((start, end)),
+        }
+    }
+    if unknown.is_empty() {
+        Ok(text)
+    } else {
+        Err(OcrError::Unrecognised(unknown))
+    }
+}
+
+/// Recognises letters in a multi-line string of `#` (or `█`) and `.` (or space).
+pub fn recognize(text: &str) -> Result<String, OcrError> {
+    let rows: Vec<Vec<bool>> = text
+        .lines()
+        .map(|l| l.chars().map(is_lit).collect())
+        .collect();
+    recognize_cells(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(font: &[(char, &str)], word: &str) -> String {
+        let glyphs: Vec<Vec<&str>> = word
+            .chars()
+            .map(|c| {
+                let (_, pattern) = font.iter().find(|(l, _)| *l == c).unwrap();
+                pattern.lines().collect()
+            })
+            .collect();
+        let height = glyphs[0].len();
+        (0..height)
+            .map(|y| glyphs.iter().map(|g| g[y]).collect::<Vec<_>>().join("."))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn small_font() {
+        let all: String = FONT_6.iter().map(|(c, _)| c).collect();
+        assert_eq!(recognize(&render(FONT_6, &all)).unwrap(), all);
+        assert_eq!(recognize(&render(FONT_6, "HELLO")).unwrap(), "HELLO");
+    }
+
+    #[test]
+    fn large_font() {
+        let all: String = FONT_10.iter().map(|(c, _)| c).collect();
+        assert_eq!(recognize(&render(FONT_10, &all)).unwrap(), all);
+    }
+
+    #[test]
+    fn padding_and_blocks() {
+        let text = format!("\n{}\n\n", render(FONT_6, "ZJ")).replace('.', " ");
+        assert_eq!(recognize(&text).unwrap(), "ZJ");
+        assert_eq!(recognize(&text.replace('#', "█")).unwrap(), "ZJ");
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(recognize("....\n...."), Err(OcrError::Empty));
+        assert_eq!(recognize("#\n#\n#"), Err(OcrError::Height(3)));
+        let mut text = render(FONT_6, "AB");
+        text = text
+            .lines()
+            .map(|l| format!("{}..##", l))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let err = recognize(&text).unwrap_err();
+        assert_eq!(err, OcrError::Unrecognised(vec![(11, 12)]));
+        assert_eq!(err.to_string(), "unrecognised glyphs at columns 11-12");
+    }
+}
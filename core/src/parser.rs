@@ -0,0 +1,367 @@
+//! A small parser-combinator toolkit for describing record formats.
+//!
+//! Parsers are plain closures from the remaining input to a value and the
+//! rest of the input, so they compose with the methods of [`Parser`]:
+//!
+//! ```
+//! use adventofcode::parser::{self, Parser};
+//!
+//! let policy = parser::unsigned::<usize>()
+//!     .skip(parser::char('-'))
+//!     .and(parser::unsigned::<usize>())
+//!     .skip(parser::char(' '))
+//!     .and(parser::letter())
+//!     .skip(parser::tag(": "))
+//!     .and(parser::rest());
+//! let (((lo, hi), c), pwd) = parser::parse_all(&policy, "1-3 a: abcde").unwrap();
+//! assert_eq!((lo, hi, c, pwd), (1, 3, 'a', "abcde"));
+//! ```
+
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("at {position}: expected {expected}")]
+pub struct ParseError {
+    /// Byte offset into the parsed input.
+    pub position: usize,
+    pub expected: String,
+}
+
+/// A failure, located by how much input was left when it happened so that
+/// positions stay meaningful however deeply parsers are nested.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Failure {
+    remaining: usize,
+    expected: String,
+}
+
+impl Failure {
+    fn new<E: Into<String>>(rest: &str, expected: E) -> Self {
+        Failure {
+            remaining: rest.len(),
+            expected: expected.into(),
+        }
+    }
+}
+
+pub type PResult<'a, O> = Result<(O, &'a str), Failure>;
+
+pub trait Parser<'a>: Sized {
+    type Output;
+
+    fn parse(&self, input: &'a str) -> PResult<'a, Self::Output>;
+
+    fn map<U, F>(self, f: F) -> impl Parser<'a, Output = U>
+    where
+        F: Fn(Self::Output) -> U,
+    {
+        move |input| {
+            let (value, rest) = self.parse(input)?;
+            Ok((f(value), rest))
+        }
+    }
+
+    /// Like [`map`](Parser::map), failing with the returned message at the
+    /// start of the mapped input.
+    fn map_res<U, F>(self, f: F) -> impl Parser<'a, Output = U>
+    where
+        F: Fn(Self::Output) -> Result<U, String>,
+    {
+        move |input| {
+            let (value, rest) = self.parse(input)?;
+            f(value)
+                .map(|value| (value, rest))
+                .map_err(|e| Failure::new(input, e))
+        }
+    }
+
+    fn and<P: Parser<'a>>(self, next: P) -> impl Parser<'a, Output = (Self::Output, P::Output)> {
+        move |input| {
+            let (a, rest) = self.parse(input)?;
+            let (b, rest) = next.parse(rest)?;
+            Ok(((a, b), rest))
+        }
+    }
+
+    /// Runs `next` and keeps only this parser's output.
+    fn skip<P: Parser<'a>>(self, next: P) -> impl Parser<'a, Output = Self::Output> {
+        self.and(next).map(|(a, _)| a)
+    }
+
+    /// Runs `next` and keeps only its output.
+    fn then<P: Parser<'a>>(self, next: P) -> impl Parser<'a, Output = P::Output> {
+        self.and(next).map(|(_, b)| b)
+    }
+
+    /// Tries `other` when this parser fails; reports whichever got further.
+    fn or<P>(self, other: P) -> impl Parser<'a, Output = Self::Output>
+    where
+        P: Parser<'a, Output = Self::Output>,
+    {
+        move |input| {
+            let first = match self.parse(input) {
+                Ok(done) => return Ok(done),
+                Err(e) => e,
+            };
+            other
+                .parse(input)
+                .map_err(|second| match first.remaining.cmp(&second.remaining) {
+                    std::cmp::Ordering::Less => first,
+                    std::cmp::Ordering::Greater => second,
+                    std::cmp::Ordering::Equal => Failure {
+                        remaining: first.remaining,
+                        expected: format!("{} or {}", first.expected, second.expected),
+                    },
+                })
+        }
+    }
+
+    fn opt(self) -> impl Parser<'a, Output = Option<Self::Output>> {
+        move |input| match self.parse(input) {
+            Ok((value, rest)) => Ok((Some(value), rest)),
+            Err(_) => Ok((None, input)),
+        }
+    }
+
+    /// One or more items separated by `sep`.
+    fn sep_by<P: Parser<'a>>(self, sep: P) -> impl Parser<'a, Output = Vec<Self::Output>> {
+        move |input| {
+            let (first, mut rest) = self.parse(input)?;
+            let mut items = vec![first];
+            while let Ok((_, after_sep)) = sep.parse(rest) {
+                match self.parse(after_sep) {
+                    Ok((item, after_item)) => {
+                        items.push(item);
+                        rest = after_item;
+                    }
+                    Err(_) => break,
+                }
+            }
+            Ok((items, rest))
+        }
+    }
+
+    /// Allows whitespace before and after.
+    fn spaced(self) -> impl Parser<'a, Output = Self::Output> {
+        ws().then(self).skip(ws())
+    }
+}
+
+impl<'a, O, F> Parser<'a> for F
+where
+    F: Fn(&'a str) -> PResult<'a, O>,
+{
+    type Output = O;
+
+    fn parse(&self, input: &'a str) -> PResult<'a, O> {
+        self(input)
+    }
+}
+
+/// Runs `parser` over the whole of `input`.
+pub fn parse_all<'a, P: Parser<'a>>(parser: &P, input: &'a str) -> Result<P::Output, ParseError> {
+    let located = |f: Failure| ParseError {
+        position: input.len() - f.remaining,
+        expected: f.expected,
+    };
+    let (value, rest) = parser.parse(input).map_err(located)?;
+    end().parse(rest).map_err(located)?;
+    Ok(value)
+}
+
+pub fn tag<'a>(expected: &'static str) -> impl Parser<'a, Output = &'a str> {
+    move |input: &'a str| match input.strip_prefix(expected) {
+        Some(rest) => Ok((&input[..expected.len()], rest)),
+        None => Err(Failure::new(input, format!("`{}`", expected))),
+    }
+}
+
+pub fn char<'a>(expected: char) -> impl Parser<'a, Output = char> {
+    satisfy(format!("`{}`", expected), move |c| c == expected)
+}
+
+/// Any of the given keywords, longest listed first wins on shared prefixes.
+pub fn one_of<'a>(words: &'static [&'static str]) -> impl Parser<'a, Output = &'a str> {
+    move |input: &'a str| {
+        words
+            .iter()
+            .find(|w| input.starts_with(**w))
+            .map(|w| (&input[..w.len()], &input[w.len()..]))
+            .ok_or_else(|| Failure::new(input, format!("one of {}", words.join(", "))))
+    }
+}
+
+pub fn satisfy<'a, F>(expected: String, pred: F) -> impl Parser<'a, Output = char>
+where
+    F: Fn(char) -> bool,
+{
+    move |input: &'a str| match input.chars().next() {
+        Some(c) if pred(c) => Ok((c, &input[c.len_utf8()..])),
+        _ => Err(Failure::new(input, expected.clone())),
+    }
+}
+
+/// The longest non-empty prefix whose characters satisfy `pred`.
+pub fn take_while1<'a, F>(expected: &'static str, pred: F) -> impl Parser<'a, Output = &'a str>
+where
+    F: Fn(char) -> bool,
+{
+    move |input: &'a str| {
+        let end = input.find(|c| !pred(c)).unwrap_or(input.len());
+        if end == 0 {
+            Err(Failure::new(input, expected))
+        } else {
+            Ok((&input[..end], &input[end..]))
+        }
+    }
+}
+
+/// Exactly `count` characters satisfying `pred`.
+pub fn take<'a, F>(
+    count: usize,
+    expected: &'static str,
+    pred: F,
+) -> impl Parser<'a, Output = &'a str>
+where
+    F: Fn(char) -> bool,
+{
+    move |input: &'a str| {
+        let mut chars = input.char_indices();
+        for _ in 0..count {
+            match chars.next() {
+                Some((_, c)) if pred(c) => {}
+                _ => return Err(Failure::new(input, format!("{} {}", count, expected))),
+            }
+        }
+        let end = chars.next().map_or(input.len(), |(i, _)| i);
+        Ok((&input[..end], &input[end..]))
+    }
+}
+
+pub fn letter<'a>() -> impl Parser<'a, Output = char> {
+    satisfy(String::from("a letter"), char::is_alphabetic)
+}
+
+pub fn digits<'a>() -> impl Parser<'a, Output = &'a str> {
+    take_while1("digits", |c| c.is_ascii_digit())
+}
+
+pub fn unsigned<'a, T: FromStr>() -> impl Parser<'a, Output = T> {
+    digits().map_res(|s| {
+        s.parse()
+            .map_err(|_| format!("a number in range, got {}", s))
+    })
+}
+
+pub fn integer<'a, T: FromStr>() -> impl Parser<'a, Output = T> {
+    move |input: &'a str| {
+        let (_, rest) = char('-').or(char('+')).opt().parse(input)?;
+        let (_, rest) = digits().parse(rest)?;
+        let text = &input[..input.len() - rest.len()];
+        text.parse()
+            .map(|n| (n, rest))
+            .map_err(|_| Failure::new(input, format!("a number in range, got {}", text)))
+    }
+}
+
+/// Everything that is left, possibly nothing.
+pub fn rest<'a>() -> impl Parser<'a, Output = &'a str> {
+    |input: &'a str| Ok((input, &input[input.len()..]))
+}
+
+/// Skips any whitespace.
+pub fn ws<'a>() -> impl Parser<'a, Output = ()> {
+    |input: &'a str| Ok(((), input.trim_start()))
+}
+
+pub fn end<'a>() -> impl Parser<'a, Output = ()> {
+    |input: &'a str| {
+        if input.is_empty() {
+            Ok(((), input))
+        } else {
+            Err(Failure::new(input, "end of input"))
+        }
+    }
+}
+
+/// `key sep value`, e.g. `hgt:183cm`.
+pub fn key_value<'a, K, S, V>(
+    key: K,
+    sep: S,
+    value: V,
+) -> impl Parser<'a, Output = (K::Output, V::Output)>
+where
+    K: Parser<'a>,
+    S: Parser<'a>,
+    V: Parser<'a>,
+{
+    key.skip(sep).and(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(parse_all(&unsigned::<u8>(), "255"), Ok(255));
+        assert_eq!(
+            parse_all(&unsigned::<u8>(), "256"),
+            Err(ParseError {
+                position: 0,
+                expected: String::from("a number in range, got 256")
+            })
+        );
+        assert_eq!(parse_all(&integer::<i32>(), "-17"), Ok(-17));
+        assert_eq!(parse_all(&integer::<i32>(), "+4"), Ok(4));
+        assert!(parse_all(&integer::<i32>(), "-").is_err());
+    }
+
+    #[test]
+    fn positions() {
+        let range = unsigned::<u32>().skip(char('-')).and(unsigned::<u32>());
+        assert_eq!(parse_all(&range, "1-3"), Ok((1, 3)));
+        let err = parse_all(&range, "1-x").unwrap_err();
+        assert_eq!(err.position, 2);
+        assert_eq!(err.to_string(), "at 2: expected digits");
+        assert_eq!(parse_all(&range, "1-3 ").unwrap_err().position, 3);
+    }
+
+    #[test]
+    fn alternatives() {
+        let unit = tag("cm").or(tag("in"));
+        assert_eq!(parse_all(&unit, "in"), Ok("in"));
+        assert_eq!(parse_all(&unit, "mm").unwrap_err().expected, "`cm` or `in`");
+        let height = unsigned::<u32>().and(unit.opt());
+        assert_eq!(parse_all(&height, "183cm"), Ok((183, Some("cm"))));
+        assert_eq!(parse_all(&height, "183"), Ok((183, None)));
+        assert_eq!(parse_all(&one_of(&["amb", "blu"]), "blu"), Ok("blu"));
+        assert!(parse_all(&one_of(&["amb", "blu"]), "blue").is_err());
+    }
+
+    #[test]
+    fn lists_and_pairs() {
+        let list = integer::<i64>().sep_by(char(',').spaced());
+        assert_eq!(parse_all(&list, "1, -2 ,3"), Ok(vec![1, -2, 3]));
+        assert!(parse_all(&list, "1,").is_err());
+        let field = key_value(take_while1("a key", char::is_alphabetic), char(':'), rest());
+        assert_eq!(parse_all(&field, "hcl:#123abc"), Ok(("hcl", "#123abc")));
+        let fields = key_value(letter(), char('='), digits()).sep_by(ws());
+        assert_eq!(
+            parse_all(&fields, "a=1  b=22"),
+            Ok(vec![('a', "1"), ('b', "22")])
+        );
+    }
+
+    #[test]
+    fn exact_counts() {
+        let hex = char('#').then(take(6, "hex digits", |c| c.is_ascii_hexdigit()));
+        assert_eq!(parse_all(&hex, "#a0b1c2"), Ok("a0b1c2"));
+        assert!(parse_all(&hex, "#a0b1c").is_err());
+        assert!(parse_all(&hex, "#a0b1c2d").is_err());
+        assert_eq!(
+            parse_all(&take(2, "letters", char::is_alphabetic), "éa"),
+            Ok("éa")
+        );
+    }
+}
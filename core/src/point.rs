@@ -0,0 +1,192 @@
+//! Points, vectors and compass directions on the integer plane.
+//!
+//! `y` grows downwards as in puzzle grids, so north is `(0, -1)` and turning
+//! right is clockwise on screen.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A displacement between two [`Point`]s.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> u64 {
+        (other - self).manhattan()
+    }
+}
+
+impl Vector {
+    pub fn new(x: i64, y: i64) -> Self {
+        Vector { x, y }
+    }
+
+    /// Length in steps along the axes.
+    pub fn manhattan(self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    /// Rotated by `quarter_turns` times 90°, clockwise when positive.
+    pub fn rotate(self, quarter_turns: i64) -> Self {
+        match quarter_turns.rem_euclid(4) {
+            0 => self,
+            1 => Vector::new(-self.y, self.x),
+            2 => -self,
+            _ => Vector::new(self.y, -self.x),
+        }
+    }
+}
+
+/// Quarter turns in `degrees`, clockwise when positive, if it is a multiple
+/// of 90.
+pub fn quarter_turns(degrees: i64) -> Option<i64> {
+    (degrees % 90 == 0).then_some(degrees / 90)
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, v: Vector) -> Point {
+        Point::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, v: Vector) {
+        *self = *self + v;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, v: Vector) -> Vector {
+        Vector::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl AddAssign for Vector {
+    fn add_assign(&mut self, v: Vector) {
+        *self = *self + v;
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, n: i64) -> Vector {
+        Vector::new(self.x * n, self.y * n)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Clockwise from north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// `N`, `E`, `S` or `W`.
+    pub fn from_letter(c: char) -> Option<Self> {
+        let i = "NESW".find(c)?;
+        Some(Self::ALL[i])
+    }
+
+    /// One step this way.
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction::North => Vector::new(0, -1),
+            Direction::East => Vector::new(1, 0),
+            Direction::South => Vector::new(0, 1),
+            Direction::West => Vector::new(-1, 0),
+        }
+    }
+
+    /// Turned by `quarter_turns` times 90°, clockwise when positive.
+    pub fn turn(self, quarter_turns: i64) -> Self {
+        let i = self as i64 + quarter_turns;
+        Self::ALL[i.rem_euclid(4) as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation() {
+        let v = Vector::new(10, -4);
+        assert_eq!(v.rotate(1), Vector::new(4, 10));
+        assert_eq!(v.rotate(2), Vector::new(-10, 4));
+        assert_eq!(v.rotate(-1), Vector::new(-4, -10));
+        assert_eq!(v.rotate(-1), v.rotate(3));
+        assert_eq!(v.rotate(4), v);
+        assert_eq!(quarter_turns(270), Some(3));
+        assert_eq!(quarter_turns(-90), Some(-1));
+        assert_eq!(quarter_turns(45), None);
+    }
+
+    #[test]
+    fn directions() {
+        assert_eq!(Direction::East.turn(1), Direction::South);
+        assert_eq!(Direction::North.turn(-1), Direction::West);
+        assert_eq!(Direction::West.turn(6), Direction::East);
+        for d in Direction::ALL {
+            assert_eq!(d.vector().rotate(1), d.turn(1).vector());
+        }
+        assert_eq!(Direction::from_letter('S'), Some(Direction::South));
+        assert_eq!(Direction::from_letter('X'), None);
+    }
+
+    #[test]
+    fn distances() {
+        let p = Point::new(17, 8) + Vector::new(0, -16);
+        assert_eq!(p, Point::new(17, -8));
+        assert_eq!(Point::ORIGIN.manhattan(p), 25);
+        assert_eq!((p - Point::new(20, -10)).manhattan(), 5);
+        assert_eq!(
+            (Vector::new(1, 2) * 3 + Vector::new(-1, 0)),
+            Vector::new(2, 6)
+        );
+    }
+}
@@ -0,0 +1,268 @@
+//! Minimal property testing on top of the [`gen`](super::gen) generators.
+//!
+//! Cases are drawn from a seeded [`Rng`]; set `PROP_SEED` and `PROP_CASES`
+//! to change the seed or the number of cases. A failing input is shrunk to a
+//! minimal counterexample before the test panics.
+//!
+//! Each day registers its solvers as [`Pair`]s, a brute-force reference and
+//! the optimised version, and one test per day walks the list:
+//!
+//! ```text
+//! const PAIRS: &[&dyn prop::Equivalence] = &[&prop::Pair {
+//!     name: "day1 pairs",
+//!     generate: expenses,
+//!     reference: pair_product_reference,
+//!     optimised: pair_product,
+//! }];
+//!
+//! #[test]
+//! fn pairs_agree() {
+//!     prop::check_all(PAIRS);
+//! }
+//! ```
+
+use std::fmt::Debug;
+use std::ops::Deref;
+
+use super::gen::Rng;
+
+pub trait Shrink: Sized {
+    /// Strictly smaller candidates, most aggressive first.
+    fn shrink(&self) -> Vec<Self>;
+}
+
+impl<T: Clone> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut out = Vec::new();
+        let mut chunk = self.len() / 2;
+        while chunk > 0 {
+            for start in (0..self.len()).step_by(chunk) {
+                let mut smaller = self[..start].to_vec();
+                smaller.extend_from_slice(&self[(start + chunk).min(self.len())..]);
+                out.push(smaller);
+            }
+            chunk /= 2;
+        }
+        if self.len() == 1 {
+            out.push(Vec::new());
+        }
+        out
+    }
+}
+
+/// Puzzle input split into lines, so counterexamples shrink line by line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lines(pub Vec<String>);
+
+impl Lines {
+    /// The lines joined back into puzzle input.
+    pub fn text(&self) -> String {
+        self.0.join("\n")
+    }
+}
+
+impl From<String> for Lines {
+    fn from(text: String) -> Self {
+        Lines(text.lines().map(String::from).collect())
+    }
+}
+
+impl Deref for Lines {
+    type Target = [String];
+
+    fn deref(&self) -> &[String] {
+        &self.0
+    }
+}
+
+impl Shrink for Lines {
+    fn shrink(&self) -> Vec<Self> {
+        self.0.shrink().into_iter().map(Lines).collect()
+    }
+}
+
+macro_rules! shrink_unsigned {
+    ($($t:ty),*) => {$(
+        impl Shrink for $t {
+            fn shrink(&self) -> Vec<Self> {
+                let mut out = Vec::new();
+                let mut delta = *self;
+                while delta > 0 {
+                    out.push(*self - delta);
+                    delta /= 2;
+                }
+                out
+            }
+        }
+    )*};
+}
+
+shrink_unsigned!(u8, u16, u32, u64, usize);
+
+/// Greedily replaces `value` with the first smaller candidate that still fails.
+pub fn minimize<T: Shrink, F: Fn(&T) -> bool>(mut value: T, fails: F) -> (T, usize) {
+    let mut steps = 0;
+    'shrink: loop {
+        for candidate in value.shrink() {
+            if fails(&candidate) {
+                value = candidate;
+                steps += 1;
+                continue 'shrink;
+            }
+        }
+        return (value, steps);
+    }
+}
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Checks `property` on generated inputs, panicking with a shrunk counterexample.
+pub fn check<T, G, P>(name: &str, mut generate: G, property: P)
+where
+    T: Shrink + Debug,
+    G: FnMut(&mut Rng) -> T,
+    P: Fn(&T) -> Result<(), String>,
+{
+    let seed = env_or("PROP_SEED", 0);
+    let cases = env_or("PROP_CASES", 100);
+    let mut rng = Rng::new(seed);
+    for case in 0..cases {
+        let input = generate(&mut rng);
+        if property(&input).is_ok() {
+            continue;
+        }
+        let (input, steps) = minimize(input, |v| property(v).is_err());
+        let reason = property(&input).unwrap_err();
+        panic!(
+            "property `{}` failed (seed {}, case {}, shrunk {} times)\ninput: {:?}\n{}",
+            name, seed, case, steps, input, reason
+        );
+    }
+}
+
+/// Checks that an optimised solver agrees with its reference implementation.
+pub fn equivalent<T, R, G, A, B>(name: &str, generate: G, reference: A, optimised: B)
+where
+    T: Shrink + Debug,
+    R: PartialEq + Debug,
+    G: FnMut(&mut Rng) -> T,
+    A: Fn(&T) -> R,
+    B: Fn(&T) -> R,
+{
+    check(name, generate, |input| {
+        let (want, got) = (reference(input), optimised(input));
+        if want == got {
+            Ok(())
+        } else {
+            Err(format!("reference: {:?}\noptimised: {:?}", want, got))
+        }
+    });
+}
+
+/// A reference implementation and an optimised one that must agree on every
+/// generated input.
+pub struct Pair<T, R> {
+    pub name: &'static str,
+    pub generate: fn(&mut Rng) -> T,
+    pub reference: fn(&T) -> R,
+    pub optimised: fn(&T) -> R,
+}
+
+/// A registered [`Pair`], whatever its input and answer types.
+pub trait Equivalence {
+    fn check(&self);
+}
+
+impl<T: Shrink + Debug, R: PartialEq + Debug> Equivalence for Pair<T, R> {
+    fn check(&self) {
+        equivalent(self.name, self.generate, self.reference, self.optimised);
+    }
+}
+
+/// Checks every pair a day registers.
+pub fn check_all(pairs: &[&dyn Equivalence]) {
+    for pair in pairs {
+        pair.check();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrink_vec() {
+        assert!(Vec::<u8>::new().shrink().is_empty());
+        assert_eq!(vec![1u8].shrink(), vec![Vec::<u8>::new()]);
+        assert_eq!(
+            vec![1, 2, 3, 4].shrink(),
+            vec![
+                vec![3, 4],
+                vec![1, 2],
+                vec![2, 3, 4],
+                vec![1, 3, 4],
+                vec![1, 2, 4],
+                vec![1, 2, 3]
+            ]
+        );
+    }
+
+    #[test]
+    fn shrink_number() {
+        assert_eq!(10u32.shrink(), vec![0, 5, 8, 9]);
+        assert_eq!(minimize(1000u32, |&n| n >= 37).0, 37);
+    }
+
+    #[test]
+    fn minimal_counterexample() {
+        let (v, _) = minimize(vec![5, 1, 12, 7, 30, 2], |v: &Vec<u32>| {
+            v.iter().sum::<u32>() > 40
+        });
+        assert_eq!(v, vec![12, 30]);
+    }
+
+    #[test]
+    fn shrink_lines() {
+        let lines = Lines::from(String::from("a\nb\n"));
+        assert_eq!(lines.text(), "a\nb");
+        assert_eq!(lines.len(), 2);
+        let smaller: Vec<String> = lines.shrink().iter().map(Lines::text).collect();
+        assert_eq!(smaller, ["b", "a"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "property `odd` failed")]
+    fn registered_pairs() {
+        const PAIRS: &[&dyn Equivalence] = &[
+            &Pair {
+                name: "even",
+                generate: |rng| rng.range(0, 9),
+                reference: |n| n % 2 == 0,
+                optimised: |n| n & 1 == 0,
+            },
+            &Pair {
+                name: "odd",
+                generate: |rng| (0..10).map(|_| rng.range(0, 9)).collect::<Vec<_>>(),
+                reference: |v| v.iter().filter(|&&n| n % 2 == 1).count(),
+                optimised: |v| v.iter().filter(|&&n| n == 1).count(),
+            },
+        ];
+        check_all(PAIRS);
+    }
+
+    #[test]
+    #[should_panic(expected = "reference: 1\noptimised: 0")]
+    fn reports_failure() {
+        equivalent(
+            "odd",
+            |rng| (0..10).map(|_| rng.range(0, 9)).collect::<Vec<_>>(),
+            |v| v.iter().filter(|&&n| n % 2 == 1).count(),
+            |v| v.iter().filter(|&&n| n == 1).count(),
+        );
+    }
+}
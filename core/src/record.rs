@@ -0,0 +1,167 @@
+//! Tokenizer for whitespace-separated `key:value` records such as day 4's
+//! passports.
+
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum RecordError {
+    #[error("token `{token}` at {span:?} has no `{separator}`")]
+    MissingSeparator {
+        token: String,
+        separator: char,
+        span: Range<usize>,
+    },
+    #[error("token `{token}` at {span:?} has an empty key")]
+    EmptyKey { token: String, span: Range<usize> },
+    #[error("duplicate key `{key}` at {span:?}, first seen at {first:?}")]
+    Duplicate {
+        key: String,
+        span: Range<usize>,
+        first: Range<usize>,
+    },
+}
+
+/// A `key:value` token; `span` is its byte range in the record.
+pub type Token<'a> = (&'a str, &'a str, Range<usize>);
+
+/// Iterator over the tokens of one record, see [`tokens`].
+pub struct Tokens<'a> {
+    input: &'a str,
+    pos: usize,
+    separator: char,
+    seen: Vec<(&'a str, Range<usize>)>,
+}
+
+/// Tokens of `input` separated by `:`.
+pub fn tokens(input: &str) -> Tokens<'_> {
+    tokens_with(input, ':')
+}
+
+/// Tokens of `input` split into key and value at the first `separator`, so
+/// `a:b:c` has key `a` and value `b:c`.
+pub fn tokens_with(input: &str, separator: char) -> Tokens<'_> {
+    Tokens {
+        input,
+        pos: 0,
+        separator,
+        seen: Vec::new(),
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Result<Token<'a>, RecordError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.input[self.pos..];
+        let start = self.pos + (rest.len() - rest.trim_start().len());
+        if start == self.input.len() {
+            self.pos = start;
+            return None;
+        }
+        let end = self.input[start..]
+            .find(char::is_whitespace)
+            .map_or(self.input.len(), |i| start + i);
+        self.pos = end;
+
+        let token = &self.input[start..end];
+        let span = start..end;
+        let (key, value) = match token.split_once(self.separator) {
+            Some(("", _)) => {
+                return Some(Err(RecordError::EmptyKey {
+                    token: token.to_string(),
+                    span,
+                }))
+            }
+            Some(kv) => kv,
+            None => {
+                return Some(Err(RecordError::MissingSeparator {
+                    token: token.to_string(),
+                    separator: self.separator,
+                    span,
+                }))
+            }
+        };
+        if let Some((_, first)) = self.seen.iter().find(|(k, _)| *k == key) {
+            return Some(Err(RecordError::Duplicate {
+                key: key.to_string(),
+                span,
+                first: first.clone(),
+            }));
+        }
+        self.seen.push((key, span.clone()));
+        Some(Ok((key, value, span)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spans() {
+        let record = "ecl:gry pid:860033327\n  hgt:183cm";
+        let parsed: Vec<_> = tokens(record).collect::<Result<_, _>>().unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                ("ecl", "gry", 0..7),
+                ("pid", "860033327", 8..21),
+                ("hgt", "183cm", 24..33),
+            ]
+        );
+        for (key, value, span) in parsed {
+            assert_eq!(&record[span], format!("{}:{}", key, value));
+        }
+        assert_eq!(tokens(" \n ").count(), 0);
+    }
+
+    #[test]
+    fn multibyte() {
+        let record = "näme:zoë\u{3000}city:Zürich";
+        let parsed: Vec<_> = tokens(record).collect::<Result<_, _>>().unwrap();
+        assert_eq!(parsed[0], ("näme", "zoë", 0..10));
+        assert_eq!(parsed[1], ("city", "Zürich", 13..25));
+    }
+
+    #[test]
+    fn separators() {
+        let parsed: Vec<_> = tokens("a:b:c").collect();
+        assert_eq!(parsed, vec![Ok(("a", "b:c", 0..5))]);
+        let parsed: Vec<_> = tokens_with("x=1 y=a:b", '=').collect();
+        assert_eq!(parsed, vec![Ok(("x", "1", 0..3)), Ok(("y", "a:b", 4..9))]);
+    }
+
+    #[test]
+    fn malformed() {
+        let parsed: Vec<_> = tokens("a:1 junk :2 b: a:3").collect();
+        assert_eq!(parsed[0], Ok(("a", "1", 0..3)));
+        assert_eq!(
+            parsed[1],
+            Err(RecordError::MissingSeparator {
+                token: String::from("junk"),
+                separator: ':',
+                span: 4..8
+            })
+        );
+        assert_eq!(
+            parsed[2],
+            Err(RecordError::EmptyKey {
+                token: String::from(":2"),
+                span: 9..11
+            })
+        );
+        assert_eq!(parsed[3], Ok(("b", "", 12..14)));
+        assert_eq!(
+            parsed[4],
+            Err(RecordError::Duplicate {
+                key: String::from("a"),
+                span: 15..18,
+                first: 0..3
+            })
+        );
+        assert_eq!(
+            parsed[4].as_ref().unwrap_err().to_string(),
+            "duplicate key `a` at 15..18, first seen at 0..3"
+        );
+    }
+}
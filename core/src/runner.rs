@@ -0,0 +1,450 @@
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+use std::time::SystemTime;
+
+use super::answers::{self, Answers};
+use super::bench::{self, Report};
+use super::gen::{self, Rng};
+use super::submit::{self, Known, Outcome};
+use super::{fs, Answer, Error};
+
+pub trait Puzzle {
+    const YEAR: u32;
+    const DAY: u32;
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+    fn part1(input: &Self::Input) -> Option<Answer>;
+    fn part2(input: &Self::Input) -> Option<Answer>;
+
+    /// Part 1 as the runner sees it. Days whose solver can fail in a way
+    /// worth reporting, rather than just finding no answer, override this.
+    fn try_part1(input: &Self::Input) -> Result<Option<Answer>, Error> {
+        Ok(Self::part1(input))
+    }
+
+    /// Part 2 as the runner sees it, see [`try_part1`](Puzzle::try_part1).
+    fn try_part2(input: &Self::Input) -> Result<Option<Answer>, Error> {
+        Ok(Self::part2(input))
+    }
+
+    /// Random but valid puzzle input, if the day has a generator.
+    fn generate(_rng: &mut Rng, _options: &gen::Options) -> Option<String> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Run,
+    Verify,
+    Record,
+    Bench,
+    Gen,
+    Submit(u32),
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    mode: Mode,
+    input: PathBuf,
+    warmup: usize,
+    samples: usize,
+    json: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+    seed: Option<u64>,
+    gen: gen::Options,
+}
+
+const USAGE: &str = "usage: dayN [verify|record|bench] [INPUT] \
+    [--warmup N] [--samples N] [--json FILE] [--baseline FILE] [--threshold PERCENT]
+       dayN gen [OUTPUT] [--seed N] [--size N] [--width N] [--valid RATIO]
+       dayN submit PART [INPUT]";
+
+pub fn default_input(day: u32) -> PathBuf {
+    PathBuf::from(format!("inputs/day{}.txt", day))
+}
+
+fn flag_value<T: std::str::FromStr>(
+    flag: &str,
+    args: &mut impl Iterator<Item = String>,
+) -> Result<T, Error> {
+    let value = args
+        .next()
+        .ok_or_else(|| Error::Parse(format!("{} expects a value", flag)))?;
+    value
+        .parse()
+        .map_err(|_| Error::Parse(format!("invalid value for {}: {}", flag, value)))
+}
+
+fn parse_args<I: Iterator<Item = String>>(day: u32, args: I) -> Result<Options, Error> {
+    let mut args = args.peekable();
+    let mode = match args.peek().map(String::as_str) {
+        Some("verify") => Mode::Verify,
+        Some("record") => Mode::Record,
+        Some("bench") => Mode::Bench,
+        Some("gen") => Mode::Gen,
+        Some("submit") => Mode::Submit(0),
+        _ => Mode::Run,
+    };
+    if mode != Mode::Run {
+        args.next();
+    }
+    let mode = match mode {
+        Mode::Submit(_) => match args.next().as_deref() {
+            Some("1") => Mode::Submit(1),
+            Some("2") => Mode::Submit(2),
+            _ => {
+                return Err(Error::Parse(format!(
+                    "submit expects part 1 or 2\n{}",
+                    USAGE
+                )))
+            }
+        },
+        mode => mode,
+    };
+
+    let mut options = Options {
+        mode,
+        input: default_input(day),
+        warmup: 3,
+        samples: 20,
+        json: None,
+        baseline: None,
+        threshold: 10.0,
+        seed: None,
+        gen: gen::Options::default(),
+    };
+    let mut input = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--warmup" => options.warmup = flag_value(&arg, &mut args)?,
+            "--samples" => options.samples = flag_value(&arg, &mut args)?,
+            "--json" => options.json = Some(flag_value(&arg, &mut args)?),
+            "--baseline" => options.baseline = Some(flag_value(&arg, &mut args)?),
+            "--threshold" => options.threshold = flag_value(&arg, &mut args)?,
+            "--seed" => options.seed = Some(flag_value(&arg, &mut args)?),
+            "--size" => options.gen.size = flag_value(&arg, &mut args)?,
+            "--width" => options.gen.width = flag_value(&arg, &mut args)?,
+            "--valid" => options.gen.valid = flag_value(&arg, &mut args)?,
+            _ if arg.starts_with("--") || input.is_some() => {
+                return Err(Error::Parse(format!(
+                    "unexpected argument `{}`\n{}",
+                    arg, USAGE
+                )))
+            }
+            _ => input = Some(PathBuf::from(arg)),
+        }
+    }
+    match (input, mode) {
+        (Some(input), _) => options.input = input,
+        // `gen` writes to stdout unless given a file.
+        (None, Mode::Gen) => options.input = PathBuf::new(),
+        (None, _) => {}
+    }
+    Ok(options)
+}
+
+/// A day's entry point, taking its arguments without the program name.
+pub type Entry = fn(Vec<String>) -> Result<bool, Error>;
+
+/// The solutions of one event, exported by each year's crate.
+pub struct Year {
+    pub year: u32,
+    /// Directory of the year's crate, where its inputs and answers live.
+    pub root: &'static str,
+    pub days: &'static [(u32, Entry)],
+}
+
+impl Year {
+    pub fn entry(&self, day: u32) -> Option<Entry> {
+        self.days
+            .iter()
+            .find(|(d, _)| *d == day)
+            .map(|(_, entry)| *entry)
+    }
+}
+
+pub fn dispatch<P: Puzzle>(args: Vec<String>) -> Result<bool, Error> {
+    let options = parse_args(P::DAY, args.into_iter())?;
+    match options.mode {
+        Mode::Bench => bench::<P>(&options),
+        Mode::Gen => generate::<P>(&options),
+        Mode::Submit(part) => submit::<P>(part, &options.input),
+        mode => run::<P>(mode, &options.input),
+    }
+}
+
+/// Exits the process with a failure status unless `result` is `Ok(true)`.
+pub fn exit(result: Result<bool, Error>) {
+    match result {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Entry point shared by the day binaries, see [`USAGE`].
+pub fn main<P: Puzzle>() {
+    exit(dispatch::<P>(std::env::args().skip(1).collect()));
+}
+
+fn labelled(label: &str, answer: Option<&Answer>) -> String {
+    match answer {
+        Some(answer) if answer.is_multiline() => format!("{}:\n{}", label, answer),
+        Some(answer) => format!("{}: {}", label, answer),
+        None => format!("{}: unknown", label),
+    }
+}
+
+fn run<P: Puzzle>(mode: Mode, path: &Path) -> Result<bool, Error> {
+    let input = P::parse(&fs::read_input(path)?)?;
+    let answers = [P::try_part1(&input), P::try_part2(&input)];
+
+    if mode == Mode::Run {
+        let mut ok = true;
+        for (part, answer) in (1..).zip(&answers) {
+            match answer {
+                Ok(answer) => {
+                    println!("{}", labelled(&format!("answer{}", part), answer.as_ref()))
+                }
+                Err(e) => {
+                    println!("answer{}: failed, {}", part, e);
+                    ok = false;
+                }
+            }
+        }
+        return Ok(ok);
+    }
+
+    let mut store = Answers::load(answers::answers_file(P::YEAR))?;
+    let mut ok = true;
+    let mut changed = false;
+    for (part, answer) in (1..).zip(answers) {
+        let label = format!("day{} part{}", P::DAY, part);
+        let answer = match answer {
+            Ok(answer) => answer,
+            Err(e) => {
+                println!("{}: failed, {}", label, e);
+                ok = false;
+                continue;
+            }
+        };
+        let expected = store.get(P::DAY, part).map(str::to_owned);
+        let shown = labelled(&label, answer.as_ref());
+        match (expected, answer) {
+            (Some(expected), Some(actual)) if actual.matches(&expected) => {
+                println!("{} ... ok", shown);
+            }
+            (Some(expected), _) => {
+                println!("{} ... MISMATCH (expected {})", shown, expected);
+                ok = false;
+            }
+            (None, Some(actual)) if mode == Mode::Record => {
+                println!("{} ... recorded", shown);
+                store.set(P::DAY, part, actual.to_string());
+                changed = true;
+            }
+            (None, _) => println!("{} ... not recorded", shown),
+        }
+    }
+
+    if changed {
+        store.save(answers::answers_file(P::YEAR))?;
+    }
+    Ok(ok)
+}
+
+fn submit<P: Puzzle>(part: u32, path: &Path) -> Result<bool, Error> {
+    let input = P::parse(&fs::read_input(path)?)?;
+    let label = format!("day{} part{}", P::DAY, part);
+    let answer = match part {
+        1 => P::try_part1(&input)?,
+        _ => P::try_part2(&input)?,
+    };
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            println!("{}", labelled(&label, None));
+            return Ok(false);
+        }
+    };
+    let shown = labelled(&label, Some(&answer));
+    let text = submit::submission(&answer)?;
+
+    let mut store = Answers::load(answers::answers_file(P::YEAR))?;
+    match submit::known(&store, P::DAY, part, &text) {
+        Some(Known::Accepted) => {
+            println!("{} ... already accepted", shown);
+            return Ok(true);
+        }
+        Some(Known::Wrong(reason)) => {
+            println!("{} ... not submitted, {}", shown, reason);
+            return Ok(false);
+        }
+        None => {}
+    }
+
+    let endpoint = submit::Endpoint::from_env()?;
+    if let Some(wait) = submit::wait_time(&store, SystemTime::now()) {
+        eprintln!("waiting {}s before submitting", wait.as_secs());
+        thread::sleep(wait);
+    }
+    let response = submit::post(&endpoint, P::YEAR, P::DAY, part, &text)?;
+    submit::record(
+        &mut store,
+        P::DAY,
+        part,
+        &text,
+        &response,
+        SystemTime::now(),
+    );
+    store.save(answers::answers_file(P::YEAR))?;
+    println!("{} ... {}", shown, response);
+    Ok(response.outcome == Outcome::Correct)
+}
+
+fn bench<P: Puzzle>(options: &Options) -> Result<bool, Error> {
+    let text = fs::read_input(&options.input)?;
+    let (warmup, samples) = (options.warmup, options.samples.max(1));
+
+    let (input, parse) = bench::measure(warmup, samples, || P::parse(&text));
+    let input = input?;
+    let (_, part1) = bench::measure(warmup, samples, || P::try_part1(&input));
+    let (_, part2) = bench::measure(warmup, samples, || P::try_part2(&input));
+
+    let report = Report {
+        warmup,
+        samples,
+        timings: [("parse", parse), ("part1", part1), ("part2", part2)]
+            .into_iter()
+            .map(|(name, stats)| (name.to_string(), stats))
+            .collect(),
+    };
+
+    println!("day{} ({} samples, {} warm-up)", P::DAY, samples, warmup);
+    println!("{:<8}{:>12}{:>12}{:>12}", "", "min", "median", "mean");
+    for (name, stats) in &report.timings {
+        println!(
+            "{:<8}{:>12}{:>12}{:>12}",
+            name,
+            bench::format_ns(stats.min_ns),
+            bench::format_ns(stats.median_ns),
+            bench::format_ns(stats.mean_ns)
+        );
+    }
+
+    let key = format!("day{}", P::DAY);
+    let mut ok = true;
+    if let Some(path) = &options.baseline {
+        match bench::load_reports(path)?.get(&key) {
+            Some(baseline) => {
+                let found = bench::regressions(&report, baseline, options.threshold);
+                for r in &found {
+                    println!(
+                        "REGRESSION {}: median {} -> {} (+{:.1}%, threshold {}%)",
+                        r.name,
+                        bench::format_ns(r.baseline_ns),
+                        bench::format_ns(r.current_ns),
+                        r.percent(),
+                        options.threshold
+                    );
+                }
+                ok = found.is_empty();
+            }
+            None => println!("no baseline for {} in {}", key, path.display()),
+        }
+    }
+
+    if let Some(path) = &options.json {
+        let mut reports = bench::load_reports(path)?;
+        reports.insert(key, report);
+        bench::save_reports(path, &reports)?;
+    }
+    Ok(ok)
+}
+
+fn generate<P: Puzzle>(options: &Options) -> Result<bool, Error> {
+    let seed = options.seed.unwrap_or_else(|| {
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH);
+        now.map_or(0, |d| d.as_nanos() as u64)
+    });
+    let text = P::generate(&mut Rng::new(seed), &options.gen)
+        .ok_or_else(|| Error::Parse(format!("day{} has no input generator", P::DAY)))?;
+    eprintln!("day{}: seed {}", P::DAY, seed);
+
+    if options.input.as_os_str().is_empty() {
+        print!("{}", text);
+    } else {
+        fs::write_output(&options.input, &text)?;
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Result<Options, Error> {
+        parse_args(3, line.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn default_options() {
+        let options = args("").unwrap();
+        assert_eq!(options.mode, Mode::Run);
+        assert_eq!(options.input, PathBuf::from("inputs/day3.txt"));
+        assert_eq!(args("verify").unwrap().mode, Mode::Verify);
+        assert_eq!(
+            args("example.txt").unwrap().input,
+            PathBuf::from("example.txt")
+        );
+    }
+
+    #[test]
+    fn bench_options() {
+        let options =
+            args("bench big.txt --samples 5 --threshold 2.5 --json out.json --warmup 0").unwrap();
+        assert_eq!(
+            options,
+            Options {
+                mode: Mode::Bench,
+                input: PathBuf::from("big.txt"),
+                warmup: 0,
+                samples: 5,
+                json: Some(PathBuf::from("out.json")),
+                baseline: None,
+                threshold: 2.5,
+                seed: None,
+                gen: gen::Options::default(),
+            }
+        );
+        assert!(args("bench --samples").is_err());
+        assert!(args("bench --samples many").is_err());
+        assert!(args("bench a.txt b.txt").is_err());
+        assert!(args("--frobnicate").is_err());
+        assert_eq!(args("submit 2").unwrap().mode, Mode::Submit(2));
+        assert_eq!(
+            args("submit 1 example.txt").unwrap().input,
+            PathBuf::from("example.txt")
+        );
+        assert!(args("submit").is_err());
+        assert!(args("submit 3").is_err());
+    }
+
+    #[test]
+    fn gen_options() {
+        let options = args("gen --seed 42 --size 1000 --valid 0.9").unwrap();
+        assert_eq!(options.mode, Mode::Gen);
+        assert_eq!(options.input, PathBuf::new());
+        assert_eq!(options.seed, Some(42));
+        assert_eq!(options.gen.size, 1000);
+        assert_eq!(options.gen.valid, 0.9);
+        assert_eq!(args("gen big.txt").unwrap().input, PathBuf::from("big.txt"));
+    }
+}
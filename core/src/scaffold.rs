@@ -0,0 +1,237 @@
+//! Generates the files for a new day, see `aoc new`.
+
+use std::path::{Path, PathBuf};
+
+use crate::{fs, runner, Error};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ScaffoldError {
+    #[error("there is no day {0}, days run from 1 to 25")]
+    InvalidDay(u32),
+    #[error("day {day} already exists: {}", path.display())]
+    Exists { day: u32, path: PathBuf },
+    #[error("cannot register the day in {}: no line starting with `{anchor}`", path.display())]
+    NoAnchor { path: PathBuf, anchor: &'static str },
+}
+
+const MODULE: &str = r#"use adventofcode::runner::Puzzle;
+use adventofcode::{fs, Answer, Error};
+
+pub struct Day{dd};
+
+impl Puzzle for Day{dd} {
+    const YEAR: u32 = {year};
+    const DAY: u32 = {day};
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        fs::parse_str(input, "\n")
+    }
+
+    fn part1(_input: &Self::Input) -> Option<Answer> {
+        None
+    }
+
+    fn part2(_input: &Self::Input) -> Option<Answer> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "";
+
+    #[test]
+    fn example() {
+        let input = Day{dd}::parse(EXAMPLE).unwrap();
+        assert_eq!(Day{dd}::part1(&input), None);
+        assert_eq!(Day{dd}::part2(&input), None);
+    }
+}
+"#;
+
+const BINARY: &str = r#"use adventofcode::runner;
+use year{year}::day{dd}::Day{dd};
+
+fn main() {
+    runner::main::<Day{dd}>();
+}
+"#;
+
+fn fill(template: &str, year: u32, day: u32) -> String {
+    template
+        .replace("{year}", &year.to_string())
+        .replace("{dd}", &format!("{:02}", day))
+        .replace("{day}", &day.to_string())
+}
+
+/// The module of day `day` in the year crate at `root`.
+pub fn module_path(root: &Path, day: u32) -> PathBuf {
+    root.join(format!("src/day{:02}.rs", day))
+}
+
+/// Inserts `line` after the last line of `text` starting with `anchor`.
+fn insert_after(
+    path: &Path,
+    text: &str,
+    anchor: &'static str,
+    line: &str,
+) -> Result<String, Error> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let last = lines
+        .iter()
+        .rposition(|l| l.starts_with(anchor))
+        .ok_or_else(|| ScaffoldError::NoAnchor {
+            path: path.to_path_buf(),
+            anchor,
+        })?;
+    lines.insert(last + 1, line);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Creates day `day` in the crate for `year` at `root`: its module, binary,
+/// example-test stub and input placeholder, registered with the runner and
+/// the example harness. Nothing is written if the day already exists.
+pub fn new_day(root: &Path, year: u32, day: u32) -> Result<Vec<PathBuf>, Error> {
+    if !(1..=25).contains(&day) {
+        return Err(ScaffoldError::InvalidDay(day).into());
+    }
+    let module = module_path(root, day);
+    let binary = root.join(format!("src/bin/day{}.rs", day));
+    for path in [&module, &binary] {
+        if path.exists() {
+            return Err(ScaffoldError::Exists {
+                day,
+                path: path.clone(),
+            }
+            .into());
+        }
+    }
+
+    let registry = root.join("src/lib.rs");
+    let text = fs::read_input(&registry)?;
+    let text = insert_after(
+        &registry,
+        &text,
+        "pub mod day",
+        &fill("pub mod day{dd};", year, day),
+    )?;
+    let text = insert_after(
+        &registry,
+        &text,
+        "    (",
+        &fill(
+            "    ({day}, runner::dispatch::<day{dd}::Day{dd}>),",
+            year,
+            day,
+        ),
+    )?;
+    let harness = root.join("tests/examples.rs");
+    let examples = fs::read_input(&harness)?;
+    let examples = insert_after(
+        &harness,
+        &examples,
+        "    (\"day",
+        &fill(
+            "    (\"day{day}\", env!(\"CARGO_BIN_EXE_day{day}\")),",
+            year,
+            day,
+        ),
+    )?;
+
+    let mut files = vec![
+        (module, fill(MODULE, year, day)),
+        (binary, fill(BINARY, year, day)),
+        (registry, text),
+        (harness, examples),
+    ];
+    let example_dir = root.join(format!("tests/examples/day{}", day));
+    let placeholders = [
+        (example_dir.join("example.txt"), String::new()),
+        (example_dir.join("example.out"), String::new()),
+        (root.join(runner::default_input(day)), String::new()),
+    ];
+    files.extend(placeholders.into_iter().filter(|(path, _)| !path.exists()));
+
+    for (path, contents) in &files {
+        fs::write_output(path, contents)?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sandbox(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("aoc-scaffold-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        fs::write_output(
+            root.join("src/lib.rs"),
+            "use crate::runner::{self, Entry};\n\npub mod day01;\n\n\
+             pub const DAYS: &[(u32, Entry)] = &[\n    (1, runner::dispatch::<day01::Day01>),\n];\n",
+        )
+        .unwrap();
+        fs::write_output(
+            root.join("tests/examples.rs"),
+            "const DAYS: &[(&str, &str)] = &[\n    (\"day1\", env!(\"CARGO_BIN_EXE_day1\")),\n];\n",
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn creates_and_registers() {
+        let root = sandbox("new");
+        let created = new_day(&root, 2020, 7).unwrap();
+        assert_eq!(created.len(), 7);
+
+        let module = std::fs::read_to_string(root.join("src/day07.rs")).unwrap();
+        assert!(module.contains("pub struct Day07;"));
+        assert!(module.contains("const YEAR: u32 = 2020;\n    const DAY: u32 = 7;"));
+        let binary = std::fs::read_to_string(root.join("src/bin/day7.rs")).unwrap();
+        assert!(binary.contains("use year2020::day07::Day07;"));
+        let registry = std::fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert!(registry.contains("pub mod day01;\npub mod day07;\n"));
+        assert!(registry.contains("Day01>),\n    (7, runner::dispatch::<day07::Day07>),\n];"));
+        let harness = std::fs::read_to_string(root.join("tests/examples.rs")).unwrap();
+        assert!(harness.contains("(\"day7\", env!(\"CARGO_BIN_EXE_day7\")),\n];"));
+        assert!(root.join("tests/examples/day7/example.out").exists());
+        assert!(root.join("inputs/day7.txt").exists());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn refuses_to_overwrite() {
+        let root = sandbox("exists");
+        fs::write_output(root.join("src/bin/day7.rs"), "fn main() {}\n").unwrap();
+        assert!(matches!(
+            new_day(&root, 2020, 7),
+            Err(Error::Scaffold(ScaffoldError::Exists { day: 7, .. }))
+        ));
+        assert!(!root.join("src/day07.rs").exists());
+        let registry = std::fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert!(!registry.contains("day07"));
+        assert!(matches!(
+            new_day(&root, 2020, 26),
+            Err(Error::Scaffold(ScaffoldError::InvalidDay(26)))
+        ));
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn keeps_existing_input() {
+        let root = sandbox("input");
+        fs::write_output(root.join("inputs/day8.txt"), "nop +0\n").unwrap();
+        let created = new_day(&root, 2020, 8).unwrap();
+        assert!(!created.contains(&root.join("inputs/day8.txt")));
+        assert_eq!(
+            std::fs::read_to_string(root.join("inputs/day8.txt")).unwrap(),
+            "nop +0\n"
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
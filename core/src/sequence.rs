@@ -0,0 +1,168 @@
+//! The elves' memory game, a Van Eck sequence: after the starting numbers,
+//! each turn says how many turns ago the previous number was last spoken,
+//! or 0 if it was new.
+
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+
+/// Default bound below which values are tracked in the flat array. Every
+/// value is smaller than the turn it is spoken on, but larger ones are rare
+/// enough that a hash map beats a flat array too big for the cache.
+pub const DENSE_LIMIT: usize = 1 << 22;
+
+/// Multiplicative hashing for the `u32` keys of the sparse map, far cheaper
+/// than the default SipHash on every turn.
+#[derive(Debug, Clone, Copy, Default)]
+struct Multiplicative(u64);
+
+const GOLDEN: u64 = 0x9e37_79b9_7f4a_7c15;
+
+impl Hasher for Multiplicative {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0 ^ b as u64).wrapping_mul(GOLDEN);
+        }
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.0 = (self.0 ^ n as u64).wrapping_mul(GOLDEN);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MemoryGame {
+    starting: Vec<u32>,
+    /// Turn each value below `limit` was last spoken on, 0 for never. Grows
+    /// as larger values come up.
+    dense: Vec<u32>,
+    limit: usize,
+    sparse: HashMap<u32, u32, BuildHasherDefault<Multiplicative>>,
+    /// Turns played so far, numbered from 1.
+    turn: u32,
+    /// Spoken on `turn`, and not yet recorded as seen.
+    last: Option<u32>,
+}
+
+impl MemoryGame {
+    pub fn new(starting: &[u32]) -> Self {
+        Self::with_dense_limit(starting, DENSE_LIMIT)
+    }
+
+    /// A game keeping values from `limit` up in the hash map.
+    pub fn with_dense_limit(starting: &[u32], limit: usize) -> Self {
+        MemoryGame {
+            starting: starting.to_vec(),
+            dense: Vec::new(),
+            limit,
+            sparse: HashMap::default(),
+            turn: 0,
+            last: None,
+        }
+    }
+
+    /// Turns played so far.
+    pub fn turn(&self) -> u32 {
+        self.turn
+    }
+
+    /// The number spoken on the last turn played.
+    pub fn last(&self) -> Option<u32> {
+        self.last
+    }
+
+    /// Records `value` as spoken on `turn`, returning when it was before.
+    fn swap_seen(&mut self, value: u32, turn: u32) -> Option<u32> {
+        let at = value as usize;
+        if at >= self.limit {
+            return self.sparse.insert(value, turn);
+        }
+        if at >= self.dense.len() {
+            let len = (at + 1).next_power_of_two().min(self.limit);
+            self.dense.resize(len, 0);
+        }
+        let before = std::mem::replace(&mut self.dense[at], turn);
+        (before != 0).then_some(before)
+    }
+
+    /// Plays one turn, returning the number spoken. Ends with the starting
+    /// numbers if there are none, or when turns no longer fit in a `u32`.
+    pub fn step(&mut self) -> Option<u32> {
+        let turn = self.turn.checked_add(1)?;
+        let before = self.last.and_then(|last| self.swap_seen(last, self.turn));
+        let next = match self.starting.get(self.turn as usize) {
+            Some(&value) => value,
+            None => {
+                self.last?;
+                before.map_or(0, |before| self.turn - before)
+            }
+        };
+        self.turn = turn;
+        self.last = Some(next);
+        Some(next)
+    }
+
+    /// The number spoken on `turn`, playing up to it. Turns already played
+    /// cannot be looked up again, apart from the last one.
+    pub fn play_until(&mut self, turn: u32) -> Option<u32> {
+        if turn < self.turn {
+            return None;
+        }
+        while self.turn < turn {
+            self.step()?;
+        }
+        self.last
+    }
+}
+
+impl Iterator for MemoryGame {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        self.step()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequence() {
+        let game = MemoryGame::new(&[0, 3, 6]);
+        let spoken: Vec<_> = game.take(10).collect();
+        assert_eq!(spoken, [0, 3, 6, 0, 3, 3, 1, 0, 4, 0]);
+        assert_eq!(MemoryGame::new(&[]).next(), None);
+        assert_eq!(
+            MemoryGame::new(&[5]).take(4).collect::<Vec<_>>(),
+            [5, 0, 0, 1]
+        );
+    }
+
+    #[test]
+    fn play_until() {
+        let mut game = MemoryGame::new(&[0, 3, 6]);
+        assert_eq!(game.play_until(2020), Some(436));
+        assert_eq!(game.play_until(2020), Some(436));
+        assert_eq!(game.play_until(10), None);
+        for (starting, spoken) in [([1, 3, 2], 1), ([2, 1, 3], 10), ([3, 1, 2], 1836)] {
+            assert_eq!(MemoryGame::new(&starting).play_until(2020), Some(spoken));
+        }
+    }
+
+    #[test]
+    fn dense_limit_only_changes_storage() {
+        let dense: Vec<_> = MemoryGame::new(&[2, 1, 3]).take(5000).collect();
+        let sparse: Vec<_> = MemoryGame::with_dense_limit(&[2, 1, 3], 8)
+            .take(5000)
+            .collect();
+        let hashed: Vec<_> = MemoryGame::with_dense_limit(&[2, 1, 3], 0)
+            .take(5000)
+            .collect();
+        assert_eq!(dense, sparse);
+        assert_eq!(dense, hashed);
+    }
+}
@@ -0,0 +1,329 @@
+//! Posts answers to an Advent of Code compatible server, see
+//! `dayN submit PART`.
+//!
+//! Outcomes are recorded in the answers store so that an answer known to be
+//! wrong, or outside the bounds learnt from "too high" and "too low"
+//! replies, is never sent twice, and any wait the server asks for is kept
+//! until the next submission.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::answers::Answers;
+use crate::{ocr, Answer, Error};
+
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+#[derive(Debug, thiserror::Error)]
+pub enum SubmitError {
+    #[error("set AOC_SESSION to the session cookie of your account")]
+    NoSession,
+    #[error("request to {url} failed: {message}")]
+    Http { url: String, message: String },
+    #[error("the server did not accept part {0}, is the previous part solved?")]
+    WrongLevel(u32),
+    #[error("unrecognised response: {0}")]
+    Unrecognised(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    RateLimited,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub outcome: Outcome,
+    /// How long the server wants us to wait before the next submission.
+    pub wait: Option<Duration>,
+}
+
+impl fmt::Display for Response {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self.outcome {
+            Outcome::Correct => "correct",
+            Outcome::Incorrect => "incorrect",
+            Outcome::TooHigh => "incorrect, too high",
+            Outcome::TooLow => "incorrect, too low",
+            Outcome::RateLimited => "rate limited",
+        })?;
+        match self.wait {
+            Some(wait) => write!(f, " (wait {}s)", wait.as_secs()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// What the store already says about an answer.
+#[derive(Debug, PartialEq)]
+pub enum Known {
+    Accepted,
+    Wrong(String),
+}
+
+pub struct Endpoint {
+    pub url: String,
+    pub session: String,
+}
+
+impl Endpoint {
+    /// Reads the session token from `AOC_SESSION`; `AOC_URL` overrides the
+    /// server.
+    pub fn from_env() -> Result<Self, Error> {
+        let session = std::env::var("AOC_SESSION").map_err(|_| SubmitError::NoSession)?;
+        let url = std::env::var("AOC_URL").unwrap_or_else(|_| String::from(DEFAULT_URL));
+        Ok(Endpoint {
+            url: url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        })
+    }
+}
+
+/// The text sent for `answer`: drawn letters are read first.
+pub fn submission(answer: &Answer) -> Result<String, Error> {
+    if answer.is_multiline() {
+        Ok(ocr::recognize(&answer.to_string())?)
+    } else {
+        Ok(answer.to_string())
+    }
+}
+
+pub fn known(store: &Answers, day: u32, part: u32, answer: &str) -> Option<Known> {
+    if let Some(accepted) = store.get(day, part) {
+        return Some(if Answer::from(answer).matches(accepted) {
+            Known::Accepted
+        } else {
+            Known::Wrong(format!("{} was already accepted", accepted))
+        });
+    }
+    store
+        .rejected(day, part)
+        .and_then(|r| r.reason(answer))
+        .map(Known::Wrong)
+}
+
+fn unix_time(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+/// How long to wait at `now` before submitting is allowed again.
+pub fn wait_time(store: &Answers, now: SystemTime) -> Option<Duration> {
+    let remaining = store.next_submit()?.checked_sub(unix_time(now))?;
+    (remaining > 0).then(|| Duration::from_secs(remaining))
+}
+
+pub fn record(
+    store: &mut Answers,
+    day: u32,
+    part: u32,
+    answer: &str,
+    response: &Response,
+    now: SystemTime,
+) {
+    match response.outcome {
+        Outcome::Correct => store.set(day, part, answer),
+        Outcome::Incorrect => store.reject(day, part, answer, None),
+        Outcome::TooHigh => store.reject(day, part, answer, Some(Ordering::Greater)),
+        Outcome::TooLow => store.reject(day, part, answer, Some(Ordering::Less)),
+        Outcome::RateLimited => {}
+    }
+    if let Some(wait) = response.wait {
+        store.set_next_submit(unix_time(now) + wait.as_secs());
+    }
+}
+
+/// The text of the page's `<article>`, with tags removed.
+fn article_text(html: &str) -> String {
+    let body = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(html, |(article, _)| article);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in body.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn count(word: &str) -> Option<u64> {
+    const WORDS: [&str; 10] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    ];
+    word.parse()
+        .ok()
+        .or_else(|| WORDS.iter().position(|w| *w == word).map(|i| i as u64 + 1))
+}
+
+/// Reads "You have 1m 38s left to wait" and "please wait one minute".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let lower = text.to_lowercase();
+    if let Some((before, _)) = lower.split_once(" left to wait") {
+        let (_, amounts) = before.rsplit_once("you have ")?;
+        let mut secs = 0;
+        for amount in amounts.split_whitespace() {
+            let unit = amount.trim_start_matches(|c: char| c.is_ascii_digit());
+            let n: u64 = amount[..amount.len() - unit.len()].parse().ok()?;
+            secs += n * match unit {
+                "h" => 3600,
+                "m" => 60,
+                "s" => 1,
+                _ => return None,
+            };
+        }
+        return Some(Duration::from_secs(secs));
+    }
+    let (_, rest) = lower.split_once("wait ")?;
+    let mut words = rest.split_whitespace();
+    let n = count(words.next()?)?;
+    match words.next()?.trim_end_matches(['.', ',']) {
+        "minute" | "minutes" => Some(Duration::from_secs(n * 60)),
+        "second" | "seconds" => Some(Duration::from_secs(n)),
+        _ => None,
+    }
+}
+
+pub fn parse_response(html: &str, part: u32) -> Result<Response, Error> {
+    let text = article_text(html);
+    let outcome = if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("too high") {
+            Outcome::TooHigh
+        } else if text.contains("too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Incorrect
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::RateLimited
+    } else if text.contains("You don't seem to be solving the right level") {
+        return Err(SubmitError::WrongLevel(part).into());
+    } else {
+        return Err(SubmitError::Unrecognised(text).into());
+    };
+    let wait = match outcome {
+        Outcome::Correct => None,
+        _ => parse_wait(&text),
+    };
+    Ok(Response { outcome, wait })
+}
+
+/// Posts `answer` for the given year, day and part.
+pub fn post(
+    endpoint: &Endpoint,
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: &str,
+) -> Result<Response, Error> {
+    let url = format!("{}/{}/day/{}/answer", endpoint.url, year, day);
+    let http = |message: String| SubmitError::Http {
+        url: url.clone(),
+        message,
+    };
+    let html = ureq::post(&url)
+        .set("Cookie", &format!("session={}", endpoint.session))
+        .set("User-Agent", "github.com/goodbin/advent-of-code")
+        .send_form(&[("level", &part.to_string()), ("answer", answer)])
+        .map_err(|e| http(e.to_string()))?
+        .into_string()
+        .map_err(|e| http(e.to_string()))?;
+    parse_response(&html, part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(message: &str) -> String {
+        format!(
+            "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            message
+        )
+    }
+
+    #[test]
+    fn outcomes() {
+        let parse = |message: &str| parse_response(&page(message), 1).unwrap();
+        assert_eq!(
+            parse("That's the right answer! You are <em>one gold star</em> closer."),
+            Response {
+                outcome: Outcome::Correct,
+                wait: None
+            }
+        );
+        assert_eq!(
+            parse("That's not the right answer; your answer is too high. Please wait one minute before trying again."),
+            Response {
+                outcome: Outcome::TooHigh,
+                wait: Some(Duration::from_secs(60))
+            }
+        );
+        assert_eq!(
+            parse("That's not the right answer; your answer is too low. please wait 5 minutes before trying again.").outcome,
+            Outcome::TooLow
+        );
+        assert_eq!(
+            parse("That's not the right answer. If you're stuck, try the subreddit.").outcome,
+            Outcome::Incorrect
+        );
+        assert_eq!(
+            parse("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 38s left to wait."),
+            Response {
+                outcome: Outcome::RateLimited,
+                wait: Some(Duration::from_secs(98))
+            }
+        );
+        assert!(matches!(
+            parse_response(&page("You don't seem to be solving the right level."), 2),
+            Err(Error::Submit(SubmitError::WrongLevel(2)))
+        ));
+        assert!(parse_response("<html>Oops</html>", 1).is_err());
+    }
+
+    #[test]
+    fn records_outcomes() {
+        let now = UNIX_EPOCH + Duration::from_secs(1000);
+        let mut store = Answers::default();
+        let too_high = Response {
+            outcome: Outcome::TooHigh,
+            wait: Some(Duration::from_secs(60)),
+        };
+        record(&mut store, 1, 2, "300", &too_high, now);
+        assert_eq!(
+            known(&store, 1, 2, "300"),
+            Some(Known::Wrong("already rejected".into()))
+        );
+        assert!(matches!(known(&store, 1, 2, "301"), Some(Known::Wrong(_))));
+        assert_eq!(known(&store, 1, 2, "299"), None);
+        assert_eq!(wait_time(&store, now), Some(Duration::from_secs(60)));
+        assert_eq!(wait_time(&store, now + Duration::from_secs(60)), None);
+
+        let correct = Response {
+            outcome: Outcome::Correct,
+            wait: None,
+        };
+        record(&mut store, 1, 2, "250", &correct, now);
+        assert_eq!(known(&store, 1, 2, "250"), Some(Known::Accepted));
+        assert!(matches!(known(&store, 1, 2, "249"), Some(Known::Wrong(_))));
+    }
+
+    #[test]
+    fn letters() {
+        let h = ["#..#", "#..#", "####", "#..#", "#..#", "#..#"];
+        assert_eq!(submission(&Answer::grid(h)).unwrap(), "H");
+        assert_eq!(submission(&Answer::from(42u8)).unwrap(), "42");
+    }
+}
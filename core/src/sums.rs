@@ -0,0 +1,158 @@
+//! Searches for pairs and contiguous ranges of numbers adding up to a target.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
+use std::ops::{Add, Range};
+
+/// Two values at distinct positions of `sorted` adding up to `target`, found
+/// by closing in from both ends.
+pub fn find_pair<T>(sorted: &[T], target: T) -> Option<(T, T)>
+where
+    T: Copy + Ord + Add<Output = T>,
+{
+    let (mut lo, mut hi) = (0, sorted.len().checked_sub(1)?);
+    while lo < hi {
+        match (sorted[lo] + sorted[hi]).cmp(&target) {
+            Ordering::Less => lo += 1,
+            Ordering::Greater => hi -= 1,
+            Ordering::Equal => return Some((sorted[lo], sorted[hi])),
+        }
+    }
+    None
+}
+
+/// The last `size` values pushed, kept sorted as the window slides so that
+/// [`find_pair`] runs on it directly.
+#[derive(Debug, Clone)]
+pub struct PairWindow<T> {
+    size: usize,
+    order: VecDeque<T>,
+    sorted: Vec<T>,
+}
+
+impl<T> PairWindow<T>
+where
+    T: Copy + Ord + Add<Output = T>,
+{
+    pub fn new(size: usize) -> Self {
+        PairWindow {
+            size,
+            order: VecDeque::with_capacity(size + 1),
+            sorted: Vec::with_capacity(size + 1),
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.order.len() == self.size
+    }
+
+    /// Adds `value`, returning the oldest value if it fell out of the window.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        let at = self.sorted.partition_point(|&v| v < value);
+        self.sorted.insert(at, value);
+        self.order.push_back(value);
+        if self.order.len() <= self.size {
+            return None;
+        }
+        let oldest = self.order.pop_front()?;
+        if let Ok(at) = self.sorted.binary_search(&oldest) {
+            self.sorted.remove(at);
+        }
+        Some(oldest)
+    }
+
+    /// Two values in the window adding up to `target`.
+    pub fn pair(&self, target: T) -> Option<(T, T)> {
+        find_pair(&self.sorted, target)
+    }
+}
+
+/// The earliest-ending range of at least `min_len` values adding up to
+/// `target`, the longest such range if several end there. Values must not be
+/// negative, so a single window sliding over them is enough.
+pub fn contiguous_range(values: &[u64], target: u64, min_len: usize) -> Option<Range<usize>> {
+    let (mut start, mut sum) = (0, 0);
+    for (end, &value) in values.iter().enumerate() {
+        sum += value;
+        while sum > target {
+            sum -= values[start];
+            start += 1;
+        }
+        // Only zeros could be added to the front, and nothing shorter fits.
+        if sum == target && end + 1 - start >= min_len {
+            return Some(start..end + 1);
+        }
+    }
+    None
+}
+
+/// Like [`contiguous_range`] for values of any sign, matching prefix sums.
+pub fn contiguous_range_signed(
+    values: &[i64],
+    target: i64,
+    min_len: usize,
+) -> Option<Range<usize>> {
+    let min_len = min_len.max(1);
+    let mut first = HashMap::new();
+    let mut prefix = vec![0; values.len() + 1];
+    for (i, &value) in values.iter().enumerate() {
+        prefix[i + 1] = prefix[i] + value;
+    }
+    for end in min_len..=values.len() {
+        // Starts become usable once the range from them is long enough.
+        let start = end - min_len;
+        first.entry(prefix[start]).or_insert(start);
+        if let Some(&start) = first.get(&(prefix[end] - target)) {
+            return Some(start..end);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pairs() {
+        assert_eq!(find_pair(&[1, 3, 5, 8], 11), Some((3, 8)));
+        assert_eq!(find_pair(&[5, 8], 10), None);
+        assert_eq!(find_pair(&[5, 5], 10), Some((5, 5)));
+        assert_eq!(find_pair::<u32>(&[], 0), None);
+    }
+
+    #[test]
+    fn sliding_window() {
+        let mut window = PairWindow::new(3);
+        for v in [10, 1, 7] {
+            assert_eq!(window.push(v), None);
+        }
+        assert!(window.is_full());
+        assert_eq!(window.pair(17), Some((7, 10)));
+        assert_eq!(window.push(7), Some(10));
+        assert_eq!(window.pair(17), None);
+        assert_eq!(window.pair(14), Some((7, 7)));
+        assert_eq!(window.push(2), Some(1));
+        assert_eq!(window.pair(8), None);
+        assert_eq!(window.pair(9), Some((2, 7)));
+    }
+
+    #[test]
+    fn ranges() {
+        let values = [
+            35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127,
+        ];
+        assert_eq!(contiguous_range(&values, 127, 2), Some(2..6));
+        assert_eq!(contiguous_range(&values, 127, 1), Some(2..6));
+        assert_eq!(contiguous_range(&values, 127, 5), None);
+        assert_eq!(contiguous_range(&[0, 0, 3, 0], 3, 1), Some(0..3));
+        assert_eq!(contiguous_range(&[], 0, 1), None);
+
+        let signed: Vec<i64> = values.iter().map(|&v| v as i64).collect();
+        assert_eq!(contiguous_range_signed(&signed, 127, 2), Some(2..6));
+        assert_eq!(contiguous_range_signed(&signed, 127, 5), None);
+        assert_eq!(contiguous_range_signed(&[0, 0, 3, 0], 3, 1), Some(0..3));
+        assert_eq!(contiguous_range_signed(&[4, -6, 5, 2], 1, 2), Some(1..4));
+        assert_eq!(contiguous_range_signed(&[4, -6, 5, 2], 7, 1), Some(2..4));
+    }
+}
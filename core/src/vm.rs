@@ -0,0 +1,298 @@
+//! The handheld console's boot code: `acc`, `jmp` and `nop` instructions
+//! with a single accumulator.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::parser::{self, Parser};
+use crate::{fs, Error};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Acc,
+    Jmp,
+    Nop,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub op: Op,
+    pub arg: i64,
+}
+
+impl FromStr for Instruction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let op = parser::one_of(&["acc", "jmp", "nop"]).map(|op| match op {
+            "acc" => Op::Acc,
+            "jmp" => Op::Jmp,
+            _ => Op::Nop,
+        });
+        let instruction = op.skip(parser::char(' ')).and(parser::integer::<i64>());
+        let (op, arg) = parser::parse_all(&instruction, s)?;
+        Ok(Instruction { op, arg })
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let op = match self.op {
+            Op::Acc => "acc",
+            Op::Jmp => "jmp",
+            Op::Nop => "nop",
+        };
+        write!(f, "{} {:+}", op, self.arg)
+    }
+}
+
+impl Instruction {
+    /// The instruction with `jmp` and `nop` swapped, if it is either.
+    pub fn flipped(self) -> Option<Self> {
+        let op = match self.op {
+            Op::Acc => return None,
+            Op::Jmp => Op::Nop,
+            Op::Nop => Op::Jmp,
+        };
+        Some(Instruction { op, ..self })
+    }
+}
+
+/// One instruction per line.
+pub fn parse_program(input: &str) -> Result<Vec<Instruction>, Error> {
+    fs::parse_str(input, "\n")
+}
+
+/// Why [`Machine::run`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    /// Ran off the end of the program, right after its last instruction.
+    Terminated,
+    /// About to execute the instruction at `ip` for the second time.
+    Loop(usize),
+    /// About to execute the instruction at a breakpoint.
+    Breakpoint(usize),
+    /// Jumped to `ip`, outside the program and not just past its end.
+    OutOfBounds(i64),
+}
+
+/// An executed instruction and the accumulator after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub ip: usize,
+    pub instruction: Instruction,
+    pub acc: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Machine<'a> {
+    program: &'a [Instruction],
+    acc: i64,
+    ip: i64,
+    visited: Vec<bool>,
+    breakpoints: BTreeSet<usize>,
+    /// Set when stopped at a breakpoint, so the next run gets past it.
+    resuming: bool,
+    trace: Option<Vec<Step>>,
+}
+
+impl<'a> Machine<'a> {
+    pub fn new(program: &'a [Instruction]) -> Self {
+        Machine {
+            program,
+            acc: 0,
+            ip: 0,
+            visited: vec![false; program.len()],
+            breakpoints: BTreeSet::new(),
+            resuming: false,
+            trace: None,
+        }
+    }
+
+    pub fn acc(&self) -> i64 {
+        self.acc
+    }
+
+    pub fn ip(&self) -> i64 {
+        self.ip
+    }
+
+    /// Stops [`run`](Machine::run) before the instruction at `ip` executes.
+    pub fn set_breakpoint(&mut self, ip: usize) {
+        self.breakpoints.insert(ip);
+    }
+
+    pub fn clear_breakpoint(&mut self, ip: usize) {
+        self.breakpoints.remove(&ip);
+    }
+
+    /// Starts recording every executed instruction, see [`trace`](Machine::trace).
+    pub fn enable_trace(&mut self) {
+        self.trace.get_or_insert_with(Vec::new);
+    }
+
+    pub fn trace(&self) -> &[Step] {
+        self.trace.as_deref().unwrap_or(&[])
+    }
+
+    /// Where the program stands before the next instruction, if it must stop.
+    fn exit(&self) -> Option<Exit> {
+        let len = self.program.len() as i64;
+        match self.ip {
+            ip if ip == len => Some(Exit::Terminated),
+            ip if !(0..len).contains(&ip) => Some(Exit::OutOfBounds(ip)),
+            ip if self.visited[ip as usize] => Some(Exit::Loop(ip as usize)),
+            _ => None,
+        }
+    }
+
+    /// Executes one instruction, or says why there is none to execute.
+    /// Breakpoints are ignored.
+    pub fn step(&mut self) -> Result<Step, Exit> {
+        if let Some(exit) = self.exit() {
+            return Err(exit);
+        }
+        let ip = self.ip as usize;
+        let instruction = self.program[ip];
+        self.visited[ip] = true;
+        match instruction.op {
+            Op::Acc => {
+                self.acc += instruction.arg;
+                self.ip += 1;
+            }
+            Op::Jmp => self.ip += instruction.arg,
+            Op::Nop => self.ip += 1,
+        }
+        let step = Step {
+            ip,
+            instruction,
+            acc: self.acc,
+        };
+        if let Some(trace) = &mut self.trace {
+            trace.push(step);
+        }
+        Ok(step)
+    }
+
+    /// Runs until the program ends, is about to repeat an instruction or
+    /// reaches a breakpoint. Running again resumes past the breakpoint.
+    pub fn run(&mut self) -> Exit {
+        let mut resuming = std::mem::take(&mut self.resuming);
+        loop {
+            if let Some(exit) = self.exit() {
+                return exit;
+            }
+            let ip = self.ip as usize;
+            if !resuming && self.breakpoints.contains(&ip) {
+                self.resuming = true;
+                return Exit::Breakpoint(ip);
+            }
+            resuming = false;
+            // Cannot fail, the exit check above covers every error.
+            let _ = self.step();
+        }
+    }
+}
+
+/// A single flipped `jmp` or `nop` that makes the program terminate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repair {
+    pub ip: usize,
+    pub instruction: Instruction,
+    /// The accumulator once the repaired program has terminated.
+    pub acc: i64,
+}
+
+/// Every single-instruction repair of `program`, in program order.
+pub fn repairs(program: &[Instruction]) -> impl Iterator<Item = Repair> + '_ {
+    let mut patched = program.to_vec();
+    (0..program.len()).filter_map(move |ip| {
+        let instruction = program[ip].flipped()?;
+        patched[ip] = instruction;
+        let mut machine = Machine::new(&patched);
+        let exit = machine.run();
+        let acc = machine.acc();
+        patched[ip] = program[ip];
+        (exit == Exit::Terminated).then_some(Repair {
+            ip,
+            instruction,
+            acc,
+        })
+    })
+}
+
+/// The first repair of `program`, see [`repairs`].
+pub fn repair(program: &[Instruction]) -> Option<Repair> {
+    repairs(program).next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str =
+        "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n";
+
+    #[test]
+    fn parse() {
+        let program = parse_program(EXAMPLE).unwrap();
+        assert_eq!(program.len(), 9);
+        assert_eq!(
+            program[5],
+            Instruction {
+                op: Op::Acc,
+                arg: -99
+            }
+        );
+        assert_eq!(program[4].to_string(), "jmp -3");
+        assert_eq!(program[0].to_string(), "nop +0");
+        assert!("mul +1".parse::<Instruction>().is_err());
+        assert!("acc 1".parse::<Instruction>().is_ok());
+        assert!("acc +".parse::<Instruction>().is_err());
+    }
+
+    #[test]
+    fn detects_loop() {
+        let program = parse_program(EXAMPLE).unwrap();
+        let mut machine = Machine::new(&program);
+        machine.enable_trace();
+        assert_eq!(machine.run(), Exit::Loop(1));
+        assert_eq!(machine.acc(), 5);
+        let ips: Vec<_> = machine.trace().iter().map(|s| s.ip).collect();
+        assert_eq!(ips, [0, 1, 2, 6, 7, 3, 4]);
+        assert_eq!(machine.step(), Err(Exit::Loop(1)));
+    }
+
+    #[test]
+    fn breakpoints() {
+        let program = parse_program(EXAMPLE).unwrap();
+        let mut machine = Machine::new(&program);
+        machine.set_breakpoint(3);
+        machine.set_breakpoint(6);
+        assert_eq!(machine.run(), Exit::Breakpoint(6));
+        assert_eq!((machine.ip(), machine.acc()), (6, 1));
+        assert_eq!(machine.run(), Exit::Breakpoint(3));
+        assert_eq!(machine.acc(), 2);
+        machine.clear_breakpoint(3);
+        assert_eq!(machine.run(), Exit::Loop(1));
+    }
+
+    #[test]
+    fn out_of_bounds() {
+        let program = parse_program("acc +1\njmp -2\n").unwrap();
+        let mut machine = Machine::new(&program);
+        assert_eq!(machine.run(), Exit::OutOfBounds(-1));
+        let program = parse_program("jmp +3\nacc +1\n").unwrap();
+        assert_eq!(Machine::new(&program).run(), Exit::OutOfBounds(3));
+    }
+
+    #[test]
+    fn repairs_single_instruction() {
+        let program = parse_program(EXAMPLE).unwrap();
+        let fixed = repair(&program).unwrap();
+        assert_eq!((fixed.ip, fixed.acc), (7, 8));
+        assert_eq!(fixed.instruction.to_string(), "nop -4");
+        assert_eq!(repairs(&program).count(), 1);
+    }
+}
@@ -0,0 +1,273 @@
+//! Re-runs a day whenever its module, input or examples change, see
+//! `aoc watch`.
+//!
+//! The day is rebuilt with cargo and run over every example and then the
+//! real input. Example answers are checked against their `.out` files and
+//! every answer is compared with the previous run, so only what changed is
+//! printed.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::answer::{self, Answer};
+use crate::scaffold::module_path;
+use crate::{fs, runner, Error};
+
+/// Modification times of the watched files that currently exist.
+pub type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Answers of one run keyed by input name (`example`, ..., `input`) and part.
+pub type Results = BTreeMap<(String, String), Answer>;
+
+#[derive(Debug, PartialEq)]
+pub enum Change {
+    Added(String, Answer),
+    Changed(String, Answer, Answer),
+    Removed(String, Answer),
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Added(label, answer) => write!(f, "+ {}: {}", label, answer),
+            Change::Changed(label, old, new) => write!(f, "~ {}: {} -> {}", label, old, new),
+            Change::Removed(label, answer) => write!(f, "- {}: {}", label, answer),
+        }
+    }
+}
+
+pub fn examples_dir(root: &Path, day: u32) -> PathBuf {
+    root.join(format!("tests/examples/day{}", day))
+}
+
+pub fn watched_files(root: &Path, day: u32) -> Result<Vec<PathBuf>, Error> {
+    let mut files = vec![
+        module_path(root, day),
+        root.join(runner::default_input(day)),
+    ];
+    files.extend(fs::list_dir(examples_dir(root, day))?);
+    Ok(files)
+}
+
+pub fn snapshot(files: &[PathBuf]) -> Snapshot {
+    files
+        .iter()
+        .filter_map(|path| {
+            let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok()?;
+            Some((path.clone(), modified))
+        })
+        .collect()
+}
+
+/// Files created, removed or modified between two snapshots.
+pub fn changed_files(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = after
+        .iter()
+        .filter(|(path, time)| before.get(*path) != Some(time))
+        .map(|(path, _)| path.clone())
+        .collect();
+    changed.extend(before.keys().filter(|p| !after.contains_key(*p)).cloned());
+    changed.sort();
+    changed
+}
+
+pub fn diff(previous: &Results, current: &Results) -> Vec<Change> {
+    let label = |(name, part): &(String, String)| format!("{} part{}", name, part);
+    let mut changes = Vec::new();
+    for (key, answer) in current {
+        match previous.get(key) {
+            None => changes.push(Change::Added(label(key), answer.clone())),
+            Some(old) if !answer.matches(&old.to_string()) => {
+                changes.push(Change::Changed(label(key), old.clone(), answer.clone()))
+            }
+            Some(_) => {}
+        }
+    }
+    for (key, answer) in previous {
+        if !current.contains_key(key) {
+            changes.push(Change::Removed(label(key), answer.clone()));
+        }
+    }
+    changes
+}
+
+fn cargo(root: &Path) -> Command {
+    let mut command = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".into()));
+    command.current_dir(root).arg("--quiet");
+    command
+}
+
+/// Builds the day, keeping cargo's diagnostics on the terminal.
+fn build(root: &Path, day: u32) -> Result<bool, Error> {
+    let mut command = cargo(root);
+    command
+        .args(["build", "--bin", &format!("day{}", day)])
+        .stdout(Stdio::null())
+        .stderr(Stdio::inherit());
+    Ok(fs::command_output(&mut command)?.status.success())
+}
+
+fn run_day(root: &Path, day: u32, input: &Path) -> Result<BTreeMap<String, Answer>, Error> {
+    let mut command = cargo(root);
+    command
+        .args(["run", "--bin", &format!("day{}", day), "--"])
+        .arg(input)
+        .stderr(Stdio::inherit());
+    let output = fs::command_output(&mut command)?;
+    Ok(answer::parse_labelled(
+        &String::from_utf8_lossy(&output.stdout),
+        "answer",
+    ))
+}
+
+/// Runs the examples and then the real input once, printing example checks
+/// and the changes since `previous`.
+pub fn run_once(root: &Path, day: u32, previous: &Results) -> Result<Results, Error> {
+    let mut results = Results::new();
+    if !build(root, day)? {
+        println!("build failed");
+        return Ok(previous.clone());
+    }
+
+    let examples = fs::list_dir(examples_dir(root, day))?;
+    for example in examples
+        .iter()
+        .filter(|p| p.extension() == Some("txt".as_ref()))
+    {
+        let name = example.file_stem().unwrap_or_default().to_string_lossy();
+        let expected = fs::read_optional(example.with_extension("out"))?
+            .map(|text| answer::parse_labelled(&text, "part"))
+            .unwrap_or_default();
+        let actual = run_day(root, day, example)?;
+        for (part, want) in &expected {
+            match actual.get(part) {
+                Some(got) if got.matches(&want.to_string()) => {
+                    println!("{} part{} ... ok", name, part)
+                }
+                got => {
+                    let got = got.map_or_else(|| String::from("<missing>"), Answer::to_string);
+                    println!(
+                        "{} part{} ... FAILED: {} (expected {})",
+                        name, part, got, want
+                    );
+                }
+            }
+        }
+        for (part, answer) in actual {
+            results.insert((name.to_string(), part), answer);
+        }
+    }
+
+    let input = root.join(runner::default_input(day));
+    if input.exists() {
+        for (part, answer) in run_day(root, day, &input)? {
+            results.insert((String::from("input"), part), answer);
+        }
+    }
+
+    let changes = diff(previous, &results);
+    if changes.is_empty() {
+        println!("answers unchanged");
+    }
+    for change in changes {
+        println!("{}", change);
+    }
+    Ok(results)
+}
+
+/// Polls the watched files every `interval`, re-running the day on change.
+pub fn watch(root: &Path, day: u32, interval: Duration) -> Result<bool, Error> {
+    let mut seen = Snapshot::new();
+    let mut results = Results::new();
+    loop {
+        let current = snapshot(&watched_files(root, day)?);
+        let changed = changed_files(&seen, &current);
+        if !changed.is_empty() {
+            let names: Vec<_> = changed
+                .iter()
+                .map(|p| p.strip_prefix(root).unwrap_or(p).display().to_string())
+                .collect();
+            println!("\n[day{}] changed: {}", day, names.join(", "));
+            results = run_once(root, day, &results)?;
+            seen = current;
+        }
+        thread::sleep(interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_changes() {
+        let t0 = SystemTime::UNIX_EPOCH;
+        let t1 = t0 + Duration::from_secs(1);
+        let before: Snapshot = [("a", t0), ("b", t0)]
+            .into_iter()
+            .map(|(p, t)| (PathBuf::from(p), t))
+            .collect();
+        assert!(changed_files(&before, &before).is_empty());
+        let after: Snapshot = [("a", t1), ("c", t0)]
+            .into_iter()
+            .map(|(p, t)| (PathBuf::from(p), t))
+            .collect();
+        assert_eq!(
+            changed_files(&before, &after),
+            vec![PathBuf::from("a"), PathBuf::from("b"), PathBuf::from("c")]
+        );
+    }
+
+    #[test]
+    fn answer_diff() {
+        let key = |name: &str, part: &str| (name.to_string(), part.to_string());
+        let previous: Results = [
+            (key("example", "1"), Answer::from(32u32)),
+            (key("input", "1"), Answer::from(4u32)),
+            (key("input", "2"), Answer::from(100u32)),
+        ]
+        .into_iter()
+        .collect();
+        let current: Results = [
+            (key("example", "1"), Answer::from("32")),
+            (key("example", "2"), Answer::from(126u32)),
+            (key("input", "1"), Answer::from(5u32)),
+        ]
+        .into_iter()
+        .collect();
+        let changes: Vec<_> = diff(&previous, &current)
+            .iter()
+            .map(Change::to_string)
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                "+ example part2: 126",
+                "~ input part1: 4 -> 5",
+                "- input part2: 100"
+            ]
+        );
+    }
+
+    #[test]
+    fn watches_examples() {
+        let root = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        fs::write_output(root.join("src/day04.rs"), "").unwrap();
+        fs::write_output(root.join("tests/examples/day4/valid.txt"), "").unwrap();
+        fs::write_output(root.join("tests/examples/day4/valid.out"), "").unwrap();
+
+        let files = watched_files(&root, 4).unwrap();
+        assert_eq!(files[0], root.join("src/day04.rs"));
+        assert_eq!(files[1], root.join("inputs/day4.txt"));
+        assert_eq!(files[3], root.join("tests/examples/day4/valid.txt"));
+        let snapshot = snapshot(&files);
+        assert!(snapshot.contains_key(&files[0]));
+        assert!(!snapshot.contains_key(&files[1]));
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}